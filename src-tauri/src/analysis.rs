@@ -1,4 +1,5 @@
-use crate::models::{HistoricalData, TauriAnalysisResult, AnalysisSettings};
+use crate::error::AppError;
+use crate::models::{HistoricalData, TauriAnalysisResult, AnalysisSettings, IssueSeverity};
 
 // ==========================================
 // Constants & Defaults (Aligned with SSOT)
@@ -12,6 +13,9 @@ pub const DEFAULT_BB_STD_DEV: f64 = 1.0;
 pub const DEFAULT_RSI_THRESHOLD: f64 = 30.0;
 pub const DEFAULT_MFI_THRESHOLD: f64 = 30.0;
 
+/// Calendar days around the last bar in which an ex-dividend date is reported
+pub const EX_DIVIDEND_WINDOW_DAYS: i64 = 5;
+
 // ==========================================
 // Technical Analysis Functions
 // ==========================================
@@ -64,11 +68,12 @@ pub fn calculate_mfi(
     volumes: &[u64],
     period: usize,
 ) -> f64 {
-    if closes.len() < period + 1 {
+    // Guard against series of different lengths instead of panicking
+    let len = closes.len().min(highs.len()).min(lows.len()).min(volumes.len());
+    if len < period + 1 {
         return 0.0;
     }
 
-    let len = closes.len();
     let mut typical_prices = Vec::with_capacity(len);
     let mut money_flows = Vec::with_capacity(len);
 
@@ -136,6 +141,31 @@ pub fn analyze_data(ticker: String, data: &HistoricalData, settings: Option<&Ana
     let rsi_threshold = settings.map(|s| s.rsi_triple_signal).unwrap_or(DEFAULT_RSI_THRESHOLD);
     let mfi_threshold = settings.map(|s| s.mfi_triple_signal).unwrap_or(DEFAULT_MFI_THRESHOLD);
 
+    // Malformed series would make the indicator loops index out of bounds
+    // or produce meaningless numbers
+    let validation_error = if !data.lengths_match() {
+        Some(AppError::InvalidData("series lengths differ".to_string()))
+    } else {
+        data.quality
+            .as_ref()
+            .and_then(|q| q.issues.iter().find(|i| i.severity == IssueSeverity::Error))
+            .map(|i| AppError::InvalidData(i.message.clone()))
+    };
+    if let Some(error) = validation_error {
+        let mut result = TauriAnalysisResult::failed(ticker, error);
+        result.data_source = Some(data.source.clone());
+        result.data_quality = data.quality.clone();
+        return result;
+    }
+
+    // Indicators only ever see bars with real prices; intraday can
+    // optionally ignore thin pre/post-market bars as well
+    let mut usable = data.without_missing();
+    if settings.map(|s| s.exclude_extended_hours).unwrap_or(false) {
+        usable = usable.regular_session_only();
+    }
+    let data = &usable;
+
     if data.closes.len() < bb_period {
         return TauriAnalysisResult {
             ticker,
@@ -147,7 +177,15 @@ pub fn analyze_data(ticker: String, data: &HistoricalData, settings: Option<&Ana
             bollinger_upper: 0.0,
             bollinger_middle: 0.0,
             triple_signal: false,
-            error: Some(format!("Not enough data (needs {})", bb_period)),
+            data_source: Some(data.source.clone()),
+            data_quality: data.quality.clone(),
+            ex_dividend: None,
+            currency: data.currency.clone(),
+            converted: None,
+            error: Some(AppError::InsufficientData {
+                needed: bb_period,
+                have: data.closes.len(),
+            }),
         };
     }
 
@@ -177,6 +215,13 @@ pub fn analyze_data(ticker: String, data: &HistoricalData, settings: Option<&Ana
         && mfi < mfi_threshold
         && current_adj_search <= bb_lower;
 
+    let ex_dividend = data.dates.last().and_then(|last| {
+        let last_day = last.date_naive();
+        data.dividends.iter().rev().find(|d| {
+            (d.date.date_naive() - last_day).num_days().abs() <= EX_DIVIDEND_WINDOW_DAYS
+        }).cloned()
+    });
+
     TauriAnalysisResult {
         ticker,
         current_price,
@@ -187,6 +232,11 @@ pub fn analyze_data(ticker: String, data: &HistoricalData, settings: Option<&Ana
         bollinger_upper: (bb_upper * 100.0).round() / 100.0,
         bollinger_middle: (bb_middle * 100.0).round() / 100.0,
         triple_signal,
+        data_source: Some(data.source.clone()),
+        data_quality: data.quality.clone(),
+        ex_dividend,
+        currency: data.currency.clone(),
+        converted: None,
         error: None,
     }
 }
@@ -0,0 +1,164 @@
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// ==========================================
+// Per-Host Circuit Breaker
+// ==========================================
+
+/// Consecutive transient failures (429, network, 5xx) that open the circuit
+const FAILURE_THRESHOLD: u32 = 5;
+const BASE_COOLDOWN: Duration = Duration::from_secs(30);
+const MAX_COOLDOWN: Duration = Duration::from_secs(300);
+/// A probe that never reported back (cancelled call) stops blocking after this
+const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Breaker state of one host, for display
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostStatus {
+    pub host: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// Seconds until the next probe is allowed (open circuits only)
+    pub retry_in_secs: Option<u64>,
+}
+
+#[derive(Default)]
+struct Circuit {
+    failures: u32,
+    /// Times the circuit opened without a success in between (grows the cool-down)
+    trips: u32,
+    open_until: Option<Instant>,
+    probe_started: Option<Instant>,
+}
+
+impl Circuit {
+    fn state(&self, now: Instant) -> CircuitState {
+        match self.open_until {
+            Some(until) if now < until => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+}
+
+/// Stops sending requests to a host that keeps failing.
+///
+/// After `FAILURE_THRESHOLD` consecutive transient failures the circuit opens
+/// and calls fail fast with `AppError::CircuitOpen` (the provider chain then
+/// moves on to the next source). Once the cool-down passes, a single probe
+/// request is let through; success closes the circuit, failure re-opens it
+/// with a longer cool-down.
+#[derive(Default)]
+pub struct CircuitBreaker {
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreaker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask to send a request to `host`
+    pub fn allow(&self, host: &str) -> Result<(), AppError> {
+        let now = Instant::now();
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(host.to_string()).or_default();
+
+        match circuit.state(now) {
+            CircuitState::Closed => Ok(()),
+            CircuitState::Open => Err(AppError::CircuitOpen {
+                host: host.to_string(),
+                retry_after: circuit
+                    .open_until
+                    .map_or(0, |until| until.saturating_duration_since(now).as_secs().max(1)),
+            }),
+            CircuitState::HalfOpen => {
+                let probing = circuit
+                    .probe_started
+                    .is_some_and(|started| now.duration_since(started) < PROBE_TIMEOUT);
+                if probing {
+                    return Err(AppError::CircuitOpen {
+                        host: host.to_string(),
+                        retry_after: 1,
+                    });
+                }
+                println!("[Rust] Circuit half-open for {}, sending probe", host);
+                circuit.probe_started = Some(now);
+                Ok(())
+            }
+        }
+    }
+
+    /// True while requests to `host` are being refused (cool-down running)
+    pub fn is_open(&self, host: &str) -> bool {
+        let circuits = self.circuits.lock().unwrap();
+        circuits
+            .get(host)
+            .is_some_and(|c| c.state(Instant::now()) == CircuitState::Open)
+    }
+
+    /// The host answered (any response that is not a transient failure)
+    pub fn record_success(&self, host: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        if let Some(circuit) = circuits.get_mut(host) {
+            if circuit.open_until.is_some() {
+                println!("[Rust] Circuit closed for {}", host);
+            }
+            *circuit = Circuit::default();
+        }
+    }
+
+    /// Rate limit, network error or 5xx from the host
+    pub fn record_failure(&self, host: &str) {
+        let now = Instant::now();
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(host.to_string()).or_default();
+        circuit.failures += 1;
+
+        let probe_failed = circuit.state(now) == CircuitState::HalfOpen;
+        if probe_failed || (circuit.open_until.is_none() && circuit.failures >= FAILURE_THRESHOLD) {
+            circuit.trips += 1;
+            let cooldown = BASE_COOLDOWN
+                .saturating_mul(1 << (circuit.trips - 1).min(8))
+                .min(MAX_COOLDOWN);
+            circuit.open_until = Some(now + cooldown);
+            circuit.probe_started = None;
+            println!(
+                "[Rust] Circuit open for {} after {} failures, cooling down {:?}",
+                host, circuit.failures, cooldown
+            );
+        }
+    }
+
+    /// Every host that has been contacted, sorted by name
+    pub fn status(&self) -> Vec<HostStatus> {
+        let now = Instant::now();
+        let circuits = self.circuits.lock().unwrap();
+        let mut status: Vec<HostStatus> = circuits
+            .iter()
+            .map(|(host, circuit)| HostStatus {
+                host: host.clone(),
+                state: circuit.state(now),
+                consecutive_failures: circuit.failures,
+                retry_in_secs: circuit
+                    .open_until
+                    .filter(|until| *until > now)
+                    .map(|until| until.duration_since(now).as_secs().max(1)),
+            })
+            .collect();
+        status.sort_by(|a, b| a.host.cmp(&b.host));
+        status
+    }
+}
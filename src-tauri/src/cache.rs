@@ -0,0 +1,331 @@
+use crate::models::{HistoricalData, HistoryRequest, Interval};
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+// ==========================================
+// In-Memory History Cache
+// ==========================================
+
+/// TTL while the exchange is trading (and shortly after the close)
+const OPEN_TTL_SECONDS: i64 = 300;
+const OPEN_TTL_INTRADAY_SECONDS: i64 = 60;
+/// Sources without a known exchange (local files, Stooq)
+const DEFAULT_TTL_SECONDS: i64 = 300;
+/// Windows that end in the past never change
+const HISTORICAL_TTL_SECONDS: i64 = 86_400;
+const MIN_TTL_SECONDS: i64 = 60;
+/// Providers settle the day's final bar (adjusted close etc.) after the close
+const CLOSE_SETTLE_MINUTES: i64 = 30;
+/// No bar for today this long after the open means the exchange is on holiday
+const HOLIDAY_GRACE_MINUTES: i64 = 30;
+
+/// Stale entries older than this are not served, even with `allowStale`
+pub const MAX_STALE_SECONDS: u64 = 86_400;
+
+/// Size bounds; least recently used entries are evicted beyond either
+pub const MAX_ENTRIES: usize = 500;
+pub const MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Cache key for a ticker's series: `"{ticker}|{request key}"`
+pub fn cache_key(ticker: &str, request: &HistoryRequest) -> String {
+    format!("{}|{}", ticker, request.cache_key())
+}
+
+fn key_ticker(key: &str) -> &str {
+    key.split_once('|').map_or(key, |(ticker, _)| ticker)
+}
+
+#[derive(Clone)]
+pub struct CacheEntry {
+    pub data: HistoricalData,
+    pub fetched_at: Instant,
+    pub expires_at: Instant,
+    last_used: Instant,
+    bytes: usize,
+}
+
+impl CacheEntry {
+    pub fn new(data: HistoricalData, request: &HistoryRequest) -> Self {
+        let ttl = cache_ttl(&data, request, Utc::now());
+        let now = Instant::now();
+        let bytes = approx_bytes(&data);
+        Self {
+            data,
+            fetched_at: now,
+            expires_at: now + ttl.to_std().unwrap_or_default(),
+            last_used: now,
+            bytes,
+        }
+    }
+
+    pub fn is_fresh(&self) -> bool {
+        Instant::now() < self.expires_at
+    }
+
+    fn is_servable(&self) -> bool {
+        self.fetched_at.elapsed().as_secs() < MAX_STALE_SECONDS
+    }
+}
+
+/// Rough heap size of a series (per-bar vectors dominate)
+fn approx_bytes(data: &HistoricalData) -> usize {
+    let bar = std::mem::size_of::<chrono::DateTime<chrono::FixedOffset>>()
+        + 5 * std::mem::size_of::<f64>()
+        + std::mem::size_of::<u64>()
+        + std::mem::size_of::<crate::models::BarSession>()
+        + std::mem::size_of::<bool>();
+    let event = std::mem::size_of::<crate::models::Split>();
+    std::mem::size_of::<HistoricalData>()
+        + data.dates.len() * bar
+        + (data.dividends.len() + data.splits.len()) * event
+}
+
+/// Result of a cache lookup
+pub enum Lookup {
+    Fresh(HistoricalData),
+    /// Expired but still servable under stale-while-revalidate
+    Stale(HistoricalData),
+    Miss,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub hits: u64,
+    pub stale_hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+    pub approx_bytes: usize,
+    pub max_entries: usize,
+    pub max_bytes: usize,
+    /// Most recently used first
+    pub items: Vec<CacheItemStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheItemStats {
+    pub key: String,
+    pub ticker: String,
+    pub source: String,
+    pub bars: usize,
+    pub age_secs: u64,
+    /// Negative once expired
+    pub expires_in_secs: i64,
+    pub approx_bytes: usize,
+}
+
+/// Size-bounded history cache with LRU eviction
+#[derive(Default)]
+pub struct HistoryCache {
+    entries: HashMap<String, CacheEntry>,
+    bytes: usize,
+    hits: u64,
+    stale_hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl HistoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look up `key`, counting the hit/miss and marking the entry as used
+    pub fn lookup(&mut self, key: &str, allow_stale: bool) -> Lookup {
+        let Some(entry) = self.entries.get_mut(key) else {
+            self.misses += 1;
+            return Lookup::Miss;
+        };
+
+        if entry.is_fresh() {
+            entry.last_used = Instant::now();
+            self.hits += 1;
+            Lookup::Fresh(entry.data.clone())
+        } else if allow_stale && entry.is_servable() {
+            entry.last_used = Instant::now();
+            self.stale_hits += 1;
+            Lookup::Stale(entry.data.clone())
+        } else {
+            self.misses += 1;
+            Lookup::Miss
+        }
+    }
+
+    pub fn insert(&mut self, key: String, entry: CacheEntry) {
+        self.bytes += entry.bytes;
+        if let Some(old) = self.entries.insert(key, entry) {
+            self.bytes -= old.bytes;
+        }
+        self.evict();
+    }
+
+    /// Drop entries nothing can serve any more, then the least recently used
+    /// ones until both bounds hold
+    fn evict(&mut self) {
+        let unservable: Vec<String> = self.entries
+            .iter()
+            .filter(|(_, e)| !e.is_servable())
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in unservable {
+            self.remove(&key);
+            self.evictions += 1;
+        }
+
+        while self.entries.len() > MAX_ENTRIES || (self.bytes > MAX_BYTES && self.entries.len() > 1) {
+            let Some(lru) = self.entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(k, _)| k.clone())
+            else {
+                break;
+            };
+            self.remove(&lru);
+            self.evictions += 1;
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> bool {
+        match self.entries.remove(key) {
+            Some(entry) => {
+                self.bytes -= entry.bytes;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove every series of `ticker` (all ranges and intervals); returns how many
+    pub fn invalidate_ticker(&mut self, ticker: &str) -> usize {
+        let keys: Vec<String> = self.entries
+            .keys()
+            .filter(|k| key_ticker(k).eq_ignore_ascii_case(ticker))
+            .cloned()
+            .collect();
+        keys.iter().filter(|k| self.remove(k)).count()
+    }
+
+    /// Drop all entries (counters are kept)
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let now = Instant::now();
+        let mut items: Vec<(Instant, CacheItemStats)> = self.entries
+            .iter()
+            .map(|(key, e)| {
+                let expires_in_secs = if e.expires_at >= now {
+                    e.expires_at.duration_since(now).as_secs() as i64
+                } else {
+                    -(now.duration_since(e.expires_at).as_secs() as i64)
+                };
+                (e.last_used, CacheItemStats {
+                    key: key.clone(),
+                    ticker: key_ticker(key).to_string(),
+                    source: e.data.source.clone(),
+                    bars: e.data.dates.len(),
+                    age_secs: e.fetched_at.elapsed().as_secs(),
+                    expires_in_secs,
+                    approx_bytes: e.bytes,
+                })
+            })
+            .collect();
+        items.sort_by_key(|(last_used, _)| std::cmp::Reverse(*last_used));
+
+        CacheStats {
+            hits: self.hits,
+            stale_hits: self.stale_hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            entries: self.entries.len(),
+            approx_bytes: self.bytes,
+            max_entries: MAX_ENTRIES,
+            max_bytes: MAX_BYTES,
+            items: items.into_iter().map(|(_, item)| item).collect(),
+        }
+    }
+}
+
+// ==========================================
+// Exchange Sessions (TTL policy)
+// ==========================================
+
+/// Regular session in exchange-local time for the exchanges we commonly see
+fn regular_hours(timezone: &str) -> (NaiveTime, NaiveTime) {
+    let hm = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+    match timezone {
+        "Asia/Seoul" => (hm(9, 0), hm(15, 30)),
+        "Asia/Tokyo" => (hm(9, 0), hm(15, 30)),
+        "Asia/Hong_Kong" => (hm(9, 30), hm(16, 0)),
+        "Asia/Shanghai" => (hm(9, 30), hm(15, 0)),
+        "Europe/London" => (hm(8, 0), hm(16, 30)),
+        "Europe/Berlin" | "Europe/Paris" | "Europe/Amsterdam" => (hm(9, 0), hm(17, 30)),
+        // US exchanges and anything unknown
+        _ => (hm(9, 30), hm(16, 0)),
+    }
+}
+
+fn is_weekday(day: NaiveDate) -> bool {
+    day.weekday().num_days_from_monday() < 5
+}
+
+/// How long a freshly fetched series stays fresh.
+///
+/// Short while the exchange is open, until the next open once it has closed
+/// (which spans the whole weekend on Friday), and long for windows that end
+/// in the past. Holidays are detected by the missing bar for today.
+pub fn cache_ttl(data: &HistoricalData, request: &HistoryRequest, now: chrono::DateTime<Utc>) -> Duration {
+    let Some(timezone) = data.timezone.as_deref() else {
+        return Duration::seconds(DEFAULT_TTL_SECONDS);
+    };
+
+    let offset = data
+        .gmtoffset
+        .and_then(chrono::FixedOffset::east_opt)
+        .unwrap_or(chrono::FixedOffset::east_opt(0).unwrap());
+    let local = now.with_timezone(&offset).naive_local();
+    let today = local.date();
+
+    if request.end.is_some_and(|end| end < today) {
+        return Duration::seconds(HISTORICAL_TTL_SECONDS);
+    }
+
+    let (open, close) = regular_hours(timezone);
+    let open_ttl = if request.interval.is_intraday() {
+        OPEN_TTL_INTRADAY_SECONDS
+    } else {
+        OPEN_TTL_SECONDS
+    };
+
+    if is_weekday(today) {
+        let open_at = today.and_time(open);
+        let settled_at = today.and_time(close) + Duration::minutes(CLOSE_SETTLE_MINUTES);
+        let last_day = data.trading_day(data.dates.len().saturating_sub(1));
+        // Weekly/monthly bars are dated at the period start, so only daily
+        // and intraday series can show a missing bar for today
+        let dated_daily = request.interval.is_intraday() || request.interval == Interval::OneDay;
+        let holiday = dated_daily
+            && local > open_at + Duration::minutes(HOLIDAY_GRACE_MINUTES)
+            && last_day.is_some_and(|d| d < today);
+
+        if local >= open_at && local < settled_at && !holiday {
+            return Duration::seconds(open_ttl);
+        }
+        if local < open_at {
+            return (open_at - local).max(Duration::seconds(MIN_TTL_SECONDS));
+        }
+    }
+
+    // Closed for the day: fresh until the next weekday's open
+    let mut next = today + Duration::days(1);
+    while !is_weekday(next) {
+        next += Duration::days(1);
+    }
+    (next.and_time(open) - local).max(Duration::seconds(MIN_TTL_SECONDS))
+}
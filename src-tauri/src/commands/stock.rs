@@ -1,11 +1,26 @@
-use crate::models::{HistoricalData, TauriAnalysisResult, YahooResponse, AnalysisSettings};
+use crate::breaker::HostStatus;
+use crate::cache::{self, CacheEntry, CacheStats, Lookup};
+use crate::concurrency::{AdaptiveLimit, Signal};
+use crate::error::AppError;
+use crate::models::{
+    AnalysisSettings, BatchEvent, BatchFinished, BatchStarted, ConvertedPrices, HistoricalData, HistoryRequest,
+    HistoryUpdatedEvent, LocalDataConfig, QuoteResult, SymbolMatch, TauriAnalysisResult, TickerEvent, TickerOutcome,
+    TickerStatus, TickerTiming,
+};
+use crate::providers::DataProvider;
+use crate::store::HistoryStore;
+use crate::symbol::Symbol;
 use crate::analysis;
+use crate::quality;
 use crate::AppState;
-use futures::future::join_all;
+use futures::future::FutureExt;
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::Serialize;
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::Semaphore;
 use std::time::Instant;
-use tauri::State;
+use tauri::ipc::Channel;
+use tauri::{Emitter, State};
 
 // ==========================================
 // User-Agent & Helper Functions
@@ -18,7 +33,9 @@ const USER_AGENTS: &[&str] = &[
     "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/121.0.0.0 Safari/537.36",
 ];
 
-const CACHE_TTL_SECONDS: u64 = 300; // 5 minutes
+/// Emitted with a `HistoryUpdatedEvent` after a stale-while-revalidate refresh
+pub const HISTORY_UPDATED_EVENT: &str = "history-updated";
+const STORE_COVERAGE_SLACK_DAYS: i64 = 7;
 
 pub fn get_random_user_agent() -> &'static str {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -36,293 +53,825 @@ pub fn get_random_user_agent() -> &'static str {
 #[tauri::command]
 pub async fn fetch_stock_data(
     ticker: String,
+    history: Option<HistoryRequest>,
     state: State<'_, AppState>
-) -> Result<HistoricalData, String> {
-    fetch_stock_data_internal(ticker, &state).await
+) -> Result<HistoricalData, AppError> {
+    fetch_stock_data_internal(ticker, &history.unwrap_or_default(), &state).await
 }
 
 pub async fn fetch_stock_data_internal(
     ticker: String,
+    request: &HistoryRequest,
     state: &AppState
-) -> Result<HistoricalData, String> {
-    let formatted_ticker = ticker.replace(".", "-");
+) -> Result<HistoricalData, AppError> {
+    request.validate()?;
+    // Canonical spelling, so BRK-B and brk.b share one cache entry and store series
+    let symbol = Symbol::parse(&ticker)?.to_string();
+    let cache_key = cache::cache_key(&symbol, request);
 
     // 1. Check Cache
-    {
-        let cache = state.cache.read().await;
-        if let Some((data, last_fetch)) = cache.get(&formatted_ticker) {
-            if last_fetch.elapsed().as_secs() < CACHE_TTL_SECONDS {
-                println!("[Rust] Cache Hit for: {}", formatted_ticker);
-                return Ok(data.clone());
-            }
+    let stale = match state.cache.write().await.lookup(&cache_key, request.allow_stale) {
+        Lookup::Fresh(data) => {
+            println!("[Rust] Cache Hit for: {}", cache_key);
+            return Ok(data);
         }
-    }
+        Lookup::Stale(data) => Some(data),
+        Lookup::Miss => None,
+    };
 
-    let url = format!(
-        "https://query1.finance.yahoo.com/v8/finance/chart/{}?range=6mo&interval=1d&includeAdjustedClose=true",
-        formatted_ticker
-    );
-    
-    println!("[Rust] Fetching data for: {}", formatted_ticker);
-    use std::io::Write;
-    let _ = std::io::stdout().flush();
-
-    let mut last_error = String::new();
-    let mut fetched_data: Option<YahooResponse> = None;
-
-    for attempt in 1..=3 {
-        if attempt > 1 {
-            println!("[Rust] Retry attempt {} for {}", attempt, formatted_ticker);
-            tokio::time::sleep(std::time::Duration::from_millis(500 * (attempt - 1) as u64)).await;
+    // 2. Join an in-flight fetch for the same key, or start one. Concurrent
+    // callers (indicator refresh, single analysis, batch) share one request.
+    let (fetch, started) = {
+        let mut inflight = state.inflight.lock().unwrap();
+        match inflight.get(&cache_key) {
+            Some(fetch) => {
+                println!("[Rust] Joining in-flight fetch for: {}", cache_key);
+                (fetch.clone(), false)
+            }
+            None => {
+                let task_state = state.clone();
+                let task_ticker = symbol.clone();
+                let task_request = request.clone();
+                let key = cache_key.clone();
+                // Runs on its own task so a cancelled caller (aborted batch
+                // job) cannot leave the other callers waiting on it
+                let fetch = tokio::spawn(async move {
+                    // Later callers go through the cache (and its TTL) again
+                    let _inflight = InflightGuard { state: task_state.clone(), key: key.clone() };
+                    load_history(&task_ticker, &task_request, &task_state, &key).await
+                })
+                .map(|joined| joined.unwrap_or_else(|e| Err(AppError::from(e))))
+                .boxed()
+                .shared();
+                inflight.insert(cache_key.clone(), fetch.clone());
+                (fetch, true)
+            }
         }
+    };
 
-        let response_result = state.client
-            .get(&url)
-            .header("User-Agent", get_random_user_agent())
-            .send()
-            .await;
-
-        match response_result {
-            Ok(response) => {
-                if response.status() == 429 {
-                    println!("[Rust] Rate Limit (429) for {}", formatted_ticker);
-                    last_error = "API_RATE_LIMIT".to_string();
-                    continue;
-                }
-
-                if !response.status().is_success() {
-                    println!("[Rust] HTTP Error {} for {}", response.status(), formatted_ticker);
-                    last_error = format!("API error: HTTP {}", response.status());
-                    continue;
-                }
-                
-                println!("[Rust] Success fetch for {}", formatted_ticker);
-
-                match response.json::<YahooResponse>().await {
+    // 3. Stale-while-revalidate: answer now, refresh in the background and
+    // tell the frontend when fresh data lands
+    if let Some(stale) = stale {
+        println!("[Rust] Serving stale cache for: {}", cache_key);
+        if started {
+            let app = state.app.clone();
+            let request = request.clone();
+            tokio::spawn(async move {
+                match fetch.await {
                     Ok(data) => {
-                        fetched_data = Some(data);
-                        break;
-                    },
-                    Err(e) => {
-                        last_error = format!("Parse error: {}", e);
-                        continue;
+                        let event = HistoryUpdatedEvent { ticker, request, data };
+                        if let Err(e) = app.emit(HISTORY_UPDATED_EVENT, event) {
+                            println!("[Rust] Failed to emit {}: {}", HISTORY_UPDATED_EVENT, e);
+                        }
                     }
+                    Err(e) => println!("[Rust] Background refresh failed for {}: {}", ticker, e),
                 }
-            }
-            Err(e) => {
-                println!("[Rust] Network error for {}: {}", formatted_ticker, e);
-                last_error = format!("Network error: {}", e);
-            }
+            });
         }
+        return Ok(stale);
     }
 
-    let data = fetched_data.ok_or(last_error)?;
+    fetch.await
+}
+
+/// Removes an in-flight entry when its fetch ends, including by panic
+struct InflightGuard {
+    state: AppState,
+    key: String,
+}
 
-    let result = data.chart.result
-        .ok_or("No data available")?
-        .into_iter()
-        .next()
-        .ok_or("Empty result")?;
-
-    let timestamps = result.timestamp.unwrap_or_default();
-    let quote = result.indicators.quote.first().ok_or("No quote data")?;
-    
-    let adj_closes = result.indicators.adjclose
-        .and_then(|ac| ac.into_iter().next())
-        .map(|ac| ac.adjclose)
-        .unwrap_or_else(|| quote.close.clone());
-
-    let dates: Vec<String> = timestamps.iter().map(|&ts| {
-        match chrono::DateTime::from_timestamp(ts, 0) {
-            Some(dt) => dt.format("%Y-%m-%d").to_string(),
-            None => format!("Invalid-Time-{}", ts)
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        if let Ok(mut inflight) = self.state.inflight.lock() {
+            inflight.remove(&self.key);
+        }
+    }
+}
+
+/// Fetch, clean up and cache one series (runs once per in-flight key)
+async fn load_history(
+    ticker: &str,
+    request: &HistoryRequest,
+    state: &AppState,
+    cache_key: &str
+) -> Result<HistoricalData, AppError> {
+    // Fetch from the configured provider (local files only when offline).
+    // Intraday bars bypass the store, which is keyed by calendar date.
+    let historical_data = if state.local_data.is_offline() {
+        let data = state.local_data.fetch_history(ticker, request).await?;
+        slice_to_window(&data, request)
+    } else {
+        match &state.store {
+            Some(store) if !request.interval.is_intraday() => {
+                fetch_with_store(ticker, request, state.provider.as_ref(), store).await?
+            }
+            _ => {
+                let data = state.provider.fetch_history(ticker, request).await?;
+                slice_to_window(&data, request)
+            }
         }
-    }).collect();
-
-    let historical_data = HistoricalData {
-        dates,
-        opens: quote.open.iter().map(|v| v.unwrap_or(0.0)).collect(),
-        highs: quote.high.iter().map(|v| v.unwrap_or(0.0)).collect(),
-        lows: quote.low.iter().map(|v| v.unwrap_or(0.0)).collect(),
-        closes: quote.close.iter().map(|v| v.unwrap_or(0.0)).collect(),
-        adj_closes: adj_closes.iter().map(|v| v.unwrap_or(0.0)).collect(),
-        volumes: quote.volume.iter().map(|v| v.unwrap_or(0)).collect(),
     };
 
-    // 5. Update Cache
+    // Handle bars the provider returned without prices, then sanity-check
+    let mut historical_data = quality::apply_gap_policy(&historical_data, request.gap_policy, request.interval);
+    let issues = quality::validate_history(&historical_data, request);
+    for issue in &issues {
+        println!("[Rust] {} data {:?}: {}", ticker, issue.severity, issue.message);
+    }
+    if let Some(report) = historical_data.quality.as_mut() {
+        report.issues = issues;
+    }
+
+    // Update Cache (TTL follows the exchange session)
     {
+        let entry = CacheEntry::new(historical_data.clone(), request);
         let mut cache = state.cache.write().await;
-        cache.insert(formatted_ticker, (historical_data.clone(), Instant::now()));
+        cache.insert(cache_key.to_string(), entry);
     }
 
     Ok(historical_data)
 }
 
+/// Trim provider output to the requested window (providers may return more)
+fn slice_to_window(data: &HistoricalData, request: &HistoryRequest) -> HistoricalData {
+    let today = chrono::Utc::now().date_naive();
+    data.slice_dates(request.window_start(today), request.end)
+}
+
+/// Run `work` against the store on the blocking pool: SQLite calls are
+/// synchronous and a batch fires many at once
+async fn on_store<T, F>(store: &Arc<HistoryStore>, work: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce(&HistoryStore) -> Result<T, AppError> + Send + 'static,
+{
+    let store = store.clone();
+    tokio::task::spawn_blocking(move || work(&store)).await?
+}
+
+/// Refresh a ticker against the persistent store.
+///
+/// When the stored series already reaches back to the requested window, only
+/// bars after it are requested. The second-to-last stored bar is re-fetched as
+/// an anchor (the last one may be a partial session); if its close or adjusted
+/// close changed, a split/dividend restated history and the whole series is
+/// reloaded. Windows that end before the last stored bar are served from disk.
+async fn fetch_with_store(
+    ticker: &str,
+    request: &HistoryRequest,
+    provider: &dyn DataProvider,
+    store: &Arc<HistoryStore>
+) -> Result<HistoricalData, AppError> {
+    let interval = request.interval.as_str();
+    let today = chrono::Utc::now().date_naive();
+    let window_start = request.window_start(today);
+
+    let (key, interval_key) = (ticker.to_string(), interval.to_string());
+    let stored = on_store(store, move |s| s.load(&key, &interval_key)).await.unwrap_or_else(|e| {
+        println!("[Rust] {}", e);
+        None
+    });
+
+    if let Some(stored) = stored.as_ref().filter(|s| s.dates.len() >= 2) {
+        let first_stored = stored.trading_day(0);
+        // A few days of slack: the window may open on a weekend or holiday
+        let covered = match (window_start, first_stored) {
+            (Some(start), Some(first)) => first <= start + chrono::Duration::days(STORE_COVERAGE_SLACK_DAYS),
+            _ => false,
+        };
+
+        let last_stored = stored.trading_day(stored.dates.len() - 1);
+        let ends_before_last = match (request.end, last_stored) {
+            (Some(end), Some(last)) => end < last,
+            _ => false,
+        };
+
+        if covered && ends_before_last {
+            println!("[Rust] Store Hit for: {} {}", ticker, request.cache_key());
+            return Ok(slice_to_window(stored, request));
+        }
+
+        let anchor_date = stored.trading_day(stored.dates.len() - 2);
+
+        if let (true, Some(since)) = (covered, anchor_date) {
+            let incremental = HistoryRequest {
+                start: Some(since),
+                end: None,
+                ..request.clone()
+            };
+            let fresh = match provider.fetch_history(ticker, &incremental).await {
+                Ok(fresh) => fresh,
+                // Upstream throttled or down: stored bars beat an error
+                Err(e) if e.is_transient() => {
+                    println!("[Rust] Serving stored history for {} ({})", ticker, e);
+                    return Ok(slice_to_window(stored, request));
+                }
+                Err(e) => return Err(e),
+            };
+
+            if overlap_matches(stored, &fresh) == Some(true) {
+                println!("[Rust] Incremental refresh for {}: {} bars since {}", ticker, fresh.dates.len(), since);
+                let (key, interval_key) = (ticker.to_string(), interval.to_string());
+                let merged = on_store(store, move |s| {
+                    s.merge(&key, &interval_key, &fresh)?;
+                    Ok(s.load(&key, &interval_key)?.unwrap_or(fresh))
+                })
+                .await?;
+                return Ok(slice_to_window(&merged, request));
+            }
+
+            println!("[Rust] History restated for {} (split/dividend), reloading", ticker);
+        }
+    }
+
+    let full = provider.fetch_history(ticker, request).await?;
+    // Replace when overlapping bars disagree (stored history is on an old
+    // adjustment basis) or when the windows are disjoint: coverage is judged
+    // by the first and last stored day, so the store must never hold a hole
+    let replace = stored.as_ref().is_some_and(|stored| {
+        overlap_matches(stored, &full) == Some(false) || !adjoins(stored, &full)
+    });
+    let (key, interval_key, bars) = (ticker.to_string(), interval.to_string(), full.clone());
+    let write = on_store(store, move |s| {
+        if replace {
+            s.replace(&key, &interval_key, &bars)
+        } else {
+            s.merge(&key, &interval_key, &bars)
+        }
+    })
+    .await;
+    if let Err(e) = write {
+        println!("[Rust] {}", e);
+    }
+    Ok(slice_to_window(&full, request))
+}
+
+/// Whether `fetched` overlaps the stored bars or continues them within a few
+/// days (weekends, holidays), so merging keeps the store one contiguous range
+fn adjoins(stored: &HistoricalData, fetched: &HistoricalData) -> bool {
+    let range = |data: &HistoricalData| {
+        Some((data.trading_day(0)?, data.trading_day(data.dates.len().checked_sub(1)?)?))
+    };
+    let (Some((stored_first, stored_last)), Some((first, last))) = (range(stored), range(fetched)) else {
+        // Nothing to leave a hole between
+        return true;
+    };
+    let slack = chrono::Duration::days(STORE_COVERAGE_SLACK_DAYS);
+    first <= stored_last + slack && last + slack >= stored_first
+}
+
+/// Compare the latest complete trading day present in both series.
+///
+/// The last stored bar is skipped since it may have been a partial session.
+/// Returns `None` when the series do not overlap.
+fn overlap_matches(stored: &HistoricalData, fresh: &HistoricalData) -> Option<bool> {
+    let complete = stored.dates.len().saturating_sub(1);
+    let (stored_idx, fresh_idx) = fresh.dates.iter().enumerate().rev().find_map(|(fresh_idx, d)| {
+        stored.dates[..complete]
+            .iter()
+            .position(|s| s.date_naive() == d.date_naive())
+            .map(|stored_idx| (stored_idx, fresh_idx))
+    })?;
+
+    let same = |a: f64, b: f64| (a - b).abs() <= a.abs().max(b.abs()) * 1e-4;
+
+    match (
+        stored.closes.get(stored_idx),
+        fresh.closes.get(fresh_idx),
+        stored.adj_closes.get(stored_idx),
+        fresh.adj_closes.get(fresh_idx),
+    ) {
+        (Some(&sc), Some(&fc), Some(&sa), Some(&fa)) => Some(same(sc, fc) && same(sa, fa)),
+        _ => Some(false),
+    }
+}
+
 #[tauri::command]
 pub async fn analyze_stock(
     symbol: String,
     settings: Option<AnalysisSettings>,
+    history: Option<HistoryRequest>,
     state: State<'_, AppState>
-) -> Result<TauriAnalysisResult, String> {
-    perform_analysis(symbol, settings, &state).await
+) -> Result<TauriAnalysisResult, AppError> {
+    perform_analysis(symbol, settings, &history.unwrap_or_default(), &state).await
 }
 
 pub async fn perform_analysis(
     symbol: String,
     settings: Option<AnalysisSettings>,
+    history: &HistoryRequest,
     state: &AppState
-) -> Result<TauriAnalysisResult, String> {
-    let data = match fetch_stock_data_internal(symbol.clone(), state).await {
+) -> Result<TauriAnalysisResult, AppError> {
+    let data = match fetch_stock_data_internal(symbol.clone(), history, state).await {
         Ok(data) => data,
-        Err(e) => return Ok(TauriAnalysisResult {
-            ticker: symbol,
-            current_price: 0.0,
-            rsi: 0.0,
-            mfi: 0.0,
-            bollinger_position: "inside".to_string(),
-            bollinger_lower: 0.0,
-            bollinger_upper: 0.0,
-            bollinger_middle: 0.0,
-            triple_signal: false,
-            error: Some(e),
-        })
+        Err(e) => return Ok(TauriAnalysisResult::failed(symbol, e)),
     };
 
-    let result = analysis::analyze_data(symbol.clone(), &data, settings.as_ref());
+    Ok(analyze_fetched(symbol, &data, settings.as_ref(), state).await)
+}
+
+/// Indicators plus currency and base-currency prices for a fetched series.
+/// Shared by the single and batch analysis commands.
+async fn analyze_fetched(
+    symbol: String,
+    data: &HistoricalData,
+    settings: Option<&AnalysisSettings>,
+    state: &AppState
+) -> TauriAnalysisResult {
+    let mut result = analysis::analyze_data(symbol.clone(), data, settings);
 
-    println!("[Rust] Analysis Result for {}: Price=${:.2}, RSI={:.2}, MFI={:.2}", 
-        symbol, result.current_price, result.rsi, result.mfi);
+    // Stored and local series may not carry a currency; the exchange implies one
+    if result.currency.is_none() {
+        result.currency = Symbol::parse(&symbol).ok().and_then(|s| s.currency());
+    }
+    let base_currency = settings
+        .and_then(|s| s.base_currency.as_deref())
+        .filter(|c| !c.trim().is_empty());
+    if let Some(base) = base_currency {
+        result.converted = convert_prices(&result, base, state).await;
+    }
 
-    Ok(result)
+    println!("[Rust] Analysis Result for {}: Price={:.2} {}, RSI={:.2}, MFI={:.2}",
+        symbol, result.current_price, result.currency.as_deref().unwrap_or("?"), result.rsi, result.mfi);
+
+    result
 }
 
-#[tauri::command]
-pub async fn analyze_multiple_stocks(
+/// Result prices in `base`; `None` (logged) when the currency or rate is unknown.
+/// A missing rate never fails the analysis itself.
+async fn convert_prices(result: &TauriAnalysisResult, base: &str, state: &AppState) -> Option<ConvertedPrices> {
+    let currency = result.currency.as_deref()?;
+    let rate = match state.fx.rate(active_provider(state), currency, base).await {
+        Ok(rate) => rate,
+        Err(e) => {
+            println!("[Rust] No {} -> {} rate for {}: {}", currency, base, result.ticker, e);
+            return None;
+        }
+    };
+
+    Some(ConvertedPrices {
+        currency: base.trim().to_uppercase(),
+        rate,
+        current_price: result.current_price * rate,
+        bollinger_lower: result.bollinger_lower * rate,
+        bollinger_upper: result.bollinger_upper * rate,
+        bollinger_middle: result.bollinger_middle * rate,
+    })
+}
+
+// ==========================================
+// Batch Jobs
+// ==========================================
+
+/// Output of one batch task that may describe a failure of its own
+trait BatchOutput {
+    fn failure(&self) -> Option<&AppError> {
+        None
+    }
+}
+
+impl BatchOutput for TauriAnalysisResult {
+    fn failure(&self) -> Option<&AppError> {
+        self.error.as_ref()
+    }
+}
+
+impl BatchOutput for HistoricalData {}
+
+/// Transient failures (rate limit, network, 5xx) get one more attempt
+const BATCH_ATTEMPTS: u32 = 2;
+const RETRY_COOLDOWN_SECS: u64 = 10;
+const MAX_RETRY_COOLDOWN_SECS: u64 = 60;
+
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
+}
+
+/// What a finished attempt says about upstream capacity (`None`: nothing,
+/// e.g. cancelled or panicked before fetching)
+fn upstream_signal(error: Option<&AppError>, fetch_ms: Option<u64>) -> Option<Signal> {
+    match error {
+        Some(AppError::RateLimited { .. } | AppError::CircuitOpen { .. }) => Some(Signal::Throttled),
+        Some(e) if e.is_transient() => Some(Signal::Failed),
+        _ => fetch_ms.map(|latency_ms| Signal::Answered { latency_ms }),
+    }
+}
+
+/// State of one running batch job
+struct BatchRun<T, F> {
+    job_id: String,
     tickers: Vec<String>,
-    settings: Option<AnalysisSettings>,
-    state: State<'_, AppState>
-) -> Result<Vec<TauriAnalysisResult>, String> {
-    let start_time = Instant::now();
-    let total_tickers = tickers.len();
-    println!("[Rust] Starting batch analysis for {} tickers", total_tickers);
-    use std::io::Write;
-    let _ = std::io::stdout().flush();
-
-    let semaphore = Arc::new(Semaphore::new(4));
-    let client = state.client.clone();
-    let cache = state.cache.clone();
-    let opt_settings = settings.clone();
-    let mut tasks = Vec::new();
-
-    for ticker in tickers {
-        let permit = semaphore.clone();
-        let current_client = client.clone();
-        let current_cache = cache.clone();
-        let current_settings = opt_settings.clone();
-        
-        let task = tokio::spawn(async move {
-            let _permit = permit.acquire().await.unwrap();
-            
-            // Re-construct a temporary state-like struct for the task
-            let temp_state = AppState {
-                client: current_client,
-                cache: current_cache,
+    state: AppState,
+    limit: Arc<AdaptiveLimit>,
+    work: F,
+    on_event: Channel<BatchEvent<T>>,
+    /// Final outcome per ticker, by request position
+    outcomes: Vec<Option<TickerOutcome>>,
+    completed: usize,
+    succeeded: usize,
+    failed: usize,
+    cancelled: usize,
+    retried: usize,
+}
+
+impl<T, F, Fut> BatchRun<T, F>
+where
+    T: BatchOutput + Serialize + Clone + Send + 'static,
+    F: Fn(String, AppState) -> Fut,
+    Fut: Future<Output = (Result<T, AppError>, TickerTiming)> + Send + 'static,
+{
+    fn send(&self, event: BatchEvent<T>) {
+        if let Err(e) = self.on_event.send(event) {
+            println!("[Rust] Failed to send batch event: {}", e);
+        }
+    }
+
+    /// Count and stream one ticker outcome (`Retrying` is not a final one)
+    fn report(&mut self, mut event: TickerEvent<T>) {
+        let timing = &event.timing;
+        println!(
+            "[Rust] {} {} {:?} (attempt {}) in {}ms: queue {:?}ms, fetch {:?}ms, analysis {:?}ms",
+            self.job_id, event.ticker, event.status, event.attempt, timing.total_ms,
+            timing.queue_ms, timing.fetch_ms, timing.analysis_ms
+        );
+
+        match event.status {
+            TickerStatus::Done => self.succeeded += 1,
+            TickerStatus::Failed => self.failed += 1,
+            TickerStatus::Cancelled => self.cancelled += 1,
+            TickerStatus::Retrying => {}
+        }
+        if event.status != TickerStatus::Retrying {
+            self.completed += 1;
+            self.outcomes[event.index] = Some(TickerOutcome {
+                ticker: event.ticker.clone(),
+                index: event.index,
+                status: event.status,
+                attempts: event.attempt,
+                error: event.error.clone(),
+                timing: event.timing.clone(),
+            });
+        }
+
+        event.completed = self.completed;
+        self.send(BatchEvent::Ticker(event));
+    }
+
+    /// Run `work` for the tickers at `indices`, streaming each outcome as it
+    /// completes. Returns the indices that failed transiently and still have
+    /// an attempt left, with the longest Retry-After any of them reported.
+    async fn run_pass(&mut self, indices: Vec<usize>, attempt: u32) -> (Vec<usize>, Option<u64>) {
+        let mut pending = FuturesUnordered::new();
+        for index in indices {
+            let limit = self.limit.clone();
+            let task = (self.work)(self.tickers[index].clone(), self.state.clone());
+            let spawned = Instant::now();
+            let handle = tokio::spawn(async move {
+                // Request pacing is done per host by the shared rate limiter
+                let _permit = limit.acquire().await;
+                let queue_ms = elapsed_ms(spawned);
+                let (result, timing) = task.await;
+                (result, TickerTiming { queue_ms: Some(queue_ms), ..timing })
+            });
+            self.state.jobs.track(&self.job_id, handle.abort_handle());
+            // The index travels with the handle, so panics and aborts keep their ticker
+            pending.push(async move { (index, spawned, handle.await) });
+        }
+
+        let mut retry = Vec::new();
+        let mut retry_after = None;
+
+        while let Some((index, spawned, joined)) = pending.next().await {
+            let (mut status, result, error, timing) = match joined {
+                Ok((Ok(output), timing)) => match output.failure().cloned() {
+                    Some(e) => (TickerStatus::Failed, Some(output), Some(e), timing),
+                    None => (TickerStatus::Done, Some(output), None, timing),
+                },
+                Ok((Err(e), timing)) => (TickerStatus::Failed, None, Some(e), timing),
+                Err(e) if e.is_cancelled() => (TickerStatus::Cancelled, None, None, TickerTiming::default()),
+                Err(e) => (TickerStatus::Failed, None, Some(AppError::from(e)), TickerTiming::default()),
             };
+            if let Some(signal) = upstream_signal(error.as_ref(), timing.fetch_ms) {
+                self.limit.record(signal);
+            }
 
-            let jitter_ms = (rand::random::<u64>() % 40) + 10;
-            tokio::time::sleep(tokio::time::Duration::from_millis(jitter_ms)).await;
-
-            match perform_analysis(ticker.clone(), current_settings, &temp_state).await {
-                Ok(res) => res,
-                Err(e) => TauriAnalysisResult {
-                    ticker,
-                    current_price: 0.0,
-                    rsi: 0.0,
-                    mfi: 0.0,
-                    bollinger_position: "inside".to_string(),
-                    bollinger_lower: 0.0,
-                    bollinger_upper: 0.0,
-                    bollinger_middle: 0.0,
-                    triple_signal: false,
-                    error: Some(e),
-                }
+            let transient = status == TickerStatus::Failed && error.as_ref().is_some_and(AppError::is_transient);
+            if transient && attempt < BATCH_ATTEMPTS && !self.state.jobs.is_cancelled(&self.job_id) {
+                status = TickerStatus::Retrying;
+                retry_after = retry_after.max(error.as_ref().and_then(AppError::retry_after));
+                retry.push(index);
             }
-        });
-        tasks.push(task);
+
+            self.report(TickerEvent {
+                job_id: self.job_id.clone(),
+                ticker: self.tickers[index].clone(),
+                index,
+                status,
+                attempt,
+                result,
+                error,
+                timing: TickerTiming { total_ms: elapsed_ms(spawned), ..timing },
+                completed: 0,
+                total: self.tickers.len(),
+            });
+        }
+
+        (retry, retry_after)
     }
+}
 
-    let results = join_all(tasks).await;
-
-    let final_results: Vec<TauriAnalysisResult> = results.into_iter().map(|res| {
-        match res {
-            Ok(inner_res) => inner_res,
-            Err(e) => TauriAnalysisResult {
-                ticker: "Unknown".to_string(),
-                current_price: 0.0,
-                rsi: 0.0,
-                mfi: 0.0,
-                bollinger_position: "inside".to_string(),
-                bollinger_lower: 0.0,
-                bollinger_upper: 0.0,
-                bollinger_middle: 0.0,
-                triple_signal: false,
-                error: Some(format!("Task panic: {}", e)),
+/// Run `work` for every ticker in the background. Starts `concurrency` at a
+/// time and adapts from there to how the upstream copes.
+///
+/// Returns the job id right away; outcomes are streamed over `on_event` as
+/// each ticker finishes. Tickers that failed transiently are run again after
+/// a cool-down. `cancel_job` aborts the tickers still pending. The final
+/// `finished` event lists every ticker's outcome in request order.
+fn start_batch<T, F, Fut>(
+    label: &'static str,
+    tickers: Vec<String>,
+    concurrency: usize,
+    state: &AppState,
+    on_event: Channel<BatchEvent<T>>,
+    work: F
+) -> String
+where
+    T: BatchOutput + Serialize + Clone + Send + 'static,
+    F: Fn(String, AppState) -> Fut + Send + 'static,
+    Fut: Future<Output = (Result<T, AppError>, TickerTiming)> + Send + 'static,
+{
+    let start_time = Instant::now();
+    let job_id = state.jobs.create();
+    let total = tickers.len();
+    println!("[Rust] Starting {} {} for {} tickers", label, job_id, total);
+
+    let mut run = BatchRun {
+        job_id: job_id.clone(),
+        tickers,
+        state: state.clone(),
+        limit: Arc::new(AdaptiveLimit::new(concurrency)),
+        work,
+        on_event,
+        outcomes: vec![None; total],
+        completed: 0,
+        succeeded: 0,
+        failed: 0,
+        cancelled: 0,
+        retried: 0,
+    };
+    run.send(BatchEvent::Started(BatchStarted { job_id: job_id.clone(), total }));
+
+    tokio::spawn(async move {
+        let mut indices: Vec<usize> = (0..total).collect();
+        for attempt in 1..=BATCH_ATTEMPTS {
+            let (retry, retry_after) = run.run_pass(indices, attempt).await;
+            if retry.is_empty() {
+                break;
             }
+
+            // Give the throttled host time to recover before the retry pass
+            let cooldown = retry_after
+                .unwrap_or(RETRY_COOLDOWN_SECS)
+                .clamp(RETRY_COOLDOWN_SECS, MAX_RETRY_COOLDOWN_SECS);
+            println!("[Rust] {} retrying {} tickers in {}s", run.job_id, retry.len(), cooldown);
+            run.retried += retry.len();
+
+            let jobs = run.state.jobs.clone();
+            tokio::select! {
+                _ = tokio::time::sleep(std::time::Duration::from_secs(cooldown)) => {}
+                _ = jobs.cancelled(&run.job_id) => {
+                    for index in retry {
+                        run.report(TickerEvent {
+                            job_id: run.job_id.clone(),
+                            ticker: run.tickers[index].clone(),
+                            index,
+                            status: TickerStatus::Cancelled,
+                            attempt: attempt + 1,
+                            result: None,
+                            error: None,
+                            timing: TickerTiming::default(),
+                            completed: 0,
+                            total,
+                        });
+                    }
+                    break;
+                }
+            }
+            indices = retry;
         }
-    }).collect();
 
-    let duration = start_time.elapsed();
-    println!("[Rust] Analyzed {} tickers in {:.2?}", total_tickers, duration);
+        run.state.jobs.finish(&run.job_id);
+        let elapsed = start_time.elapsed();
+        let concurrency = run.limit.summary();
+        println!(
+            "[Rust] {} {} finished in {:.2?}: {} ok, {} failed, {} cancelled, {} retried (concurrency {}..{}, ended at {})",
+            label, run.job_id, elapsed, run.succeeded, run.failed, run.cancelled, run.retried,
+            concurrency.min, concurrency.max, concurrency.last
+        );
+        run.send(BatchEvent::Finished(BatchFinished {
+            job_id: run.job_id.clone(),
+            total,
+            succeeded: run.succeeded,
+            failed: run.failed,
+            cancelled: run.cancelled,
+            retried: run.retried,
+            elapsed_ms: elapsed.as_millis() as u64,
+            concurrency,
+            outcomes: run.outcomes.iter().flatten().cloned().collect(),
+        }));
+    });
+
+    job_id
+}
 
-    Ok(final_results)
+/// Analyze many tickers; returns a job id and streams results over `on_event`
+#[tauri::command]
+pub async fn analyze_multiple_stocks(
+    tickers: Vec<String>,
+    settings: Option<AnalysisSettings>,
+    history: Option<HistoryRequest>,
+    on_event: Channel<BatchEvent<TauriAnalysisResult>>,
+    state: State<'_, AppState>
+) -> Result<String, AppError> {
+    let history = history.unwrap_or_default();
+    history.validate()?;
+
+    Ok(start_batch("batch analysis", tickers, 4, &state, on_event, move |ticker, task_state| {
+        let settings = settings.clone();
+        let history = history.clone();
+        async move {
+            let mut timing = TickerTiming::default();
+
+            let fetch_start = Instant::now();
+            let fetched = fetch_stock_data_internal(ticker.clone(), &history, &task_state).await;
+            timing.fetch_ms = Some(elapsed_ms(fetch_start));
+
+            let result = match fetched {
+                Ok(data) => {
+                    let analysis_start = Instant::now();
+                    let result = analyze_fetched(ticker, &data, settings.as_ref(), &task_state).await;
+                    timing.analysis_ms = Some(elapsed_ms(analysis_start));
+                    result
+                }
+                Err(e) => TauriAnalysisResult::failed(ticker, e),
+            };
+            (Ok(result), timing)
+        }
+    }))
 }
 
+/// Fetch many tickers; returns a job id and streams series over `on_event`
 #[tauri::command]
 pub async fn fetch_multiple_stocks(
     tickers: Vec<String>,
+    history: Option<HistoryRequest>,
+    on_event: Channel<BatchEvent<HistoricalData>>,
     state: State<'_, AppState>
-) -> Result<Vec<Result<HistoricalData, String>>, String> {
-    let start_time = Instant::now();
-    let total_tickers = tickers.len();
-    println!("[Rust] Starting concurrent fetch for {} tickers", total_tickers);
-
-    let semaphore = Arc::new(Semaphore::new(2)); 
-    let client = state.client.clone();
-    let cache = state.cache.clone();
-    let mut tasks = Vec::new();
-
-    for ticker in tickers {
-        let permit = semaphore.clone();
-        let current_client = client.clone();
-        let current_cache = cache.clone();
-        
-        let task = tokio::spawn(async move {
-            let _permit = permit.acquire().await.unwrap();
-            
-            let temp_state = AppState {
-                client: current_client,
-                cache: current_cache,
-            };
+) -> Result<String, AppError> {
+    let history = history.unwrap_or_default();
+    history.validate()?;
+
+    Ok(start_batch("concurrent fetch", tickers, 2, &state, on_event, move |ticker, task_state| {
+        let history = history.clone();
+        async move {
+            let fetch_start = Instant::now();
+            let result = fetch_stock_data_internal(ticker, &history, &task_state).await;
+            let timing = TickerTiming { fetch_ms: Some(elapsed_ms(fetch_start)), ..Default::default() };
+            (result, timing)
+        }
+    }))
+}
 
-            let jitter_ms = (rand::random::<u64>() % 40) + 10;
-            tokio::time::sleep(tokio::time::Duration::from_millis(jitter_ms)).await;
+/// Stop a running batch job; tickers already finished keep their results.
+/// Returns false if the job is unknown or already done.
+#[tauri::command]
+pub fn cancel_job(job_id: String, state: State<'_, AppState>) -> bool {
+    let cancelled = state.jobs.cancel(&job_id);
+    if cancelled {
+        println!("[Rust] Cancelling {}", job_id);
+    }
+    cancelled
+}
 
-            fetch_stock_data_internal(ticker, &temp_state).await
-        });
-        tasks.push(task);
+// ==========================================
+// Quotes
+// ==========================================
+
+/// Local files only when offline, otherwise the provider chain
+fn active_provider(state: &AppState) -> Arc<dyn DataProvider> {
+    if state.local_data.is_offline() {
+        state.local_data.clone()
+    } else {
+        state.provider.clone()
     }
+}
+
+/// Last price, change and day range per ticker, in request order. Much
+/// cheaper than `fetch_stock_data`, so the overlay can poll it every few seconds.
+#[tauri::command]
+pub async fn fetch_quotes(tickers: Vec<String>, state: State<'_, AppState>) -> Result<Vec<QuoteResult>, AppError> {
+    let mut quotes = active_provider(&state).fetch_quotes(&tickers).await?.into_iter();
+
+    Ok(tickers
+        .into_iter()
+        .map(|ticker| {
+            // A provider that answered short leaves the rest without a quote
+            let quote = quotes
+                .next()
+                .unwrap_or_else(|| Err(AppError::NotFound(format!("No quote for {}", ticker))));
+            match quote {
+                Ok(quote) => QuoteResult { ticker, quote: Some(quote), error: None },
+                Err(e) => {
+                    println!("[Rust] Quote failed for {}: {}", ticker, e);
+                    QuoteResult { ticker, quote: None, error: Some(e) }
+                }
+            }
+        })
+        .collect())
+}
+
+// ==========================================
+// Symbol Search
+// ==========================================
+
+/// Listings matching a ticker or company name, best match first
+#[tauri::command]
+pub async fn search_symbols(query: String, state: State<'_, AppState>) -> Result<Vec<SymbolMatch>, AppError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    active_provider(&state).search_symbols(query).await
+}
+
+/// Check that a ticker is actually listed before it is saved.
+/// `NotFound` when no listing matches, `InvalidSymbol` when it cannot be parsed.
+#[tauri::command]
+pub async fn validate_symbol(ticker: String, state: State<'_, AppState>) -> Result<SymbolMatch, AppError> {
+    let symbol = Symbol::parse(&ticker)?;
+
+    active_provider(&state)
+        .lookup_symbol(&symbol)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No listing matches {}", symbol)))
+}
 
-    let results = join_all(tasks).await;
+#[tauri::command]
+pub fn get_local_data_config(state: State<'_, AppState>) -> LocalDataConfig {
+    state.local_data.config()
+}
 
-    let final_results: Vec<Result<HistoricalData, String>> = results.into_iter().map(|res| {
-        match res {
-            Ok(inner_res) => inner_res,
-            Err(e) => Err(format!("Task execution error: {}", e)),
+#[tauri::command]
+pub async fn set_local_data_config(
+    config: LocalDataConfig,
+    state: State<'_, AppState>
+) -> Result<LocalDataConfig, AppError> {
+    if let Some(dir) = &config.dir {
+        if !std::path::Path::new(dir).is_dir() {
+            return Err(AppError::InvalidSettings(format!("not a directory: {}", dir)));
         }
-    }).collect();
+    }
 
-    let duration = start_time.elapsed();
-    println!("[Rust] Fetched {} tickers in {:.2?}", total_tickers, duration);
+    println!("[Rust] Local data config: {:?}", config);
+    state.local_data.set_config(config.clone());
 
-    Ok(final_results)
+    // Previously cached series may have come from another source
+    state.cache.write().await.clear();
+
+    Ok(config)
+}
+
+/// Circuit breaker state per upstream host (e.g. "Yahoo throttled, retrying in 40s")
+#[tauri::command]
+pub fn get_upstream_status(state: State<'_, AppState>) -> Vec<HostStatus> {
+    state.breaker.status()
+}
+
+#[tauri::command]
+pub async fn get_cache_stats(state: State<'_, AppState>) -> Result<CacheStats, AppError> {
+    Ok(state.cache.read().await.stats())
+}
+
+/// Force a refetch of one ticker: drops its cached series and stored bars
+#[tauri::command]
+pub async fn invalidate_cache(ticker: String, state: State<'_, AppState>) -> Result<usize, AppError> {
+    let ticker = Symbol::parse(&ticker)?.to_string();
+    let removed = state.cache.write().await.invalidate_ticker(&ticker);
+    if let Some(store) = &state.store {
+        let key = ticker.clone();
+        on_store(store, move |s| s.delete_ticker(&key)).await?;
+    }
+    println!("[Rust] Invalidated {} cached series for {}", removed, ticker);
+    Ok(removed)
+}
+
+/// Drop every cached series and all stored bars
+#[tauri::command]
+pub async fn clear_cache(state: State<'_, AppState>) -> Result<(), AppError> {
+    state.cache.write().await.clear();
+    if let Some(store) = &state.store {
+        on_store(store, |s| s.clear()).await?;
+    }
+    println!("[Rust] Cache cleared");
+    Ok(())
 }
@@ -0,0 +1,156 @@
+use crate::models::ConcurrencySummary;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+// ==========================================
+// Adaptive Concurrency (AIMD)
+// ==========================================
+
+const MIN_LIMIT: f64 = 1.0;
+const MAX_LIMIT: f64 = 16.0;
+/// A response this many times slower than the fastest one seen means the
+/// upstream is queueing us
+const SLOW_FACTOR: u64 = 3;
+/// Never call a response under this slow (cache hits make the fastest tiny)
+const SLOW_FLOOR_MS: u64 = 1_500;
+/// At most one decrease per window, so a burst of 429s halves the limit once
+const DECREASE_INTERVAL: Duration = Duration::from_secs(2);
+
+const THROTTLED_DECREASE: f64 = 0.5;
+const FAILED_DECREASE: f64 = 0.75;
+
+/// How a task that held a permit went
+#[derive(Debug, Clone, Copy)]
+pub enum Signal {
+    /// The upstream answered (including "not found") after `latency_ms`
+    Answered { latency_ms: u64 },
+    /// 429 or an open circuit
+    Throttled,
+    /// Network error or 5xx
+    Failed,
+}
+
+struct Inner {
+    limit: f64,
+    in_flight: usize,
+    fastest_ms: Option<u64>,
+    last_decrease: Option<Instant>,
+    min: f64,
+    max: f64,
+    throttled: usize,
+}
+
+/// Concurrency limit for one batch job that grows by one task per round of
+/// quick answers (additive increase) and shrinks sharply on throttling or
+/// errors (multiplicative decrease).
+pub struct AdaptiveLimit {
+    initial: usize,
+    inner: Mutex<Inner>,
+    released: Notify,
+}
+
+/// Held while a task runs; frees its slot when dropped
+pub struct LimitPermit {
+    limit: Arc<AdaptiveLimit>,
+}
+
+impl Drop for LimitPermit {
+    fn drop(&mut self) {
+        self.limit.inner.lock().unwrap().in_flight -= 1;
+        self.limit.released.notify_waiters();
+    }
+}
+
+impl AdaptiveLimit {
+    pub fn new(initial: usize) -> Self {
+        let limit = (initial as f64).clamp(MIN_LIMIT, MAX_LIMIT);
+        Self {
+            initial: limit as usize,
+            inner: Mutex::new(Inner {
+                limit,
+                in_flight: 0,
+                fastest_ms: None,
+                last_decrease: None,
+                min: limit,
+                max: limit,
+                throttled: 0,
+            }),
+            released: Notify::new(),
+        }
+    }
+
+    /// Wait for a free slot under the current limit
+    pub async fn acquire(self: &Arc<Self>) -> LimitPermit {
+        loop {
+            let released = self.released.notified();
+            tokio::pin!(released);
+            // Register before checking so a release in between is not missed
+            released.as_mut().enable();
+
+            {
+                let mut inner = self.inner.lock().unwrap();
+                if (inner.in_flight as f64) < inner.limit.floor() {
+                    inner.in_flight += 1;
+                    return LimitPermit { limit: self.clone() };
+                }
+            }
+            released.await;
+        }
+    }
+
+    /// Adjust the limit after a task finished
+    pub fn record(&self, signal: Signal) {
+        let mut inner = self.inner.lock().unwrap();
+        let before = inner.limit.floor();
+
+        match signal {
+            Signal::Answered { latency_ms } => {
+                let fastest = inner.fastest_ms.map_or(latency_ms, |f| f.min(latency_ms));
+                inner.fastest_ms = Some(fastest);
+                let slow = latency_ms > (fastest * SLOW_FACTOR).max(SLOW_FLOOR_MS);
+                // Slow answers hold the limit; a full round of quick ones adds a slot
+                if !slow {
+                    inner.limit = (inner.limit + 1.0 / inner.limit).min(MAX_LIMIT);
+                }
+            }
+            Signal::Throttled => {
+                inner.throttled += 1;
+                decrease(&mut inner, THROTTLED_DECREASE);
+            }
+            Signal::Failed => decrease(&mut inner, FAILED_DECREASE),
+        }
+
+        inner.min = inner.min.min(inner.limit);
+        inner.max = inner.max.max(inner.limit);
+        let after = inner.limit.floor();
+        drop(inner);
+
+        if after != before {
+            println!("[Rust] Batch concurrency {} -> {}", before, after);
+        }
+        if after > before {
+            self.released.notify_waiters();
+        }
+    }
+
+    pub fn summary(&self) -> ConcurrencySummary {
+        let inner = self.inner.lock().unwrap();
+        ConcurrencySummary {
+            initial: self.initial,
+            min: inner.min.floor() as usize,
+            max: inner.max.floor() as usize,
+            last: inner.limit.floor() as usize,
+            throttled: inner.throttled,
+        }
+    }
+}
+
+fn decrease(inner: &mut Inner, factor: f64) {
+    let now = Instant::now();
+    if inner.last_decrease.is_some_and(|at| now.duration_since(at) < DECREASE_INTERVAL) {
+        return;
+    }
+    inner.last_decrease = Some(now);
+    inner.limit = (inner.limit * factor).max(MIN_LIMIT);
+}
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+
+// ==========================================
+// Structured Errors (sent to the frontend)
+// ==========================================
+
+/// Errors returned by commands and attached to analysis results.
+///
+/// Serialized as `{ code, message, ...details }`. `code` is stable and meant
+/// for the frontend to switch on and localize; `message` is English text for
+/// logs and as a fallback.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "ErrorPayload", from = "ErrorPayload")]
+pub enum AppError {
+    /// HTTP 429 from a data provider; `retry_after` in seconds when sent
+    RateLimited { retry_after: Option<u64> },
+    /// Host failed repeatedly; requests are skipped until `retry_after` seconds pass
+    CircuitOpen { host: String, retry_after: u64 },
+    /// Non-success HTTP status other than 429/404
+    Http { status: u16 },
+    /// Connection, DNS, TLS or timeout failure
+    Network(String),
+    /// Response or file could not be decoded
+    Parse(String),
+    /// No data for the symbol (unknown ticker, missing local file)
+    NotFound(String),
+    /// The ticker text cannot be parsed as a symbol
+    InvalidSymbol(String),
+    /// The provider knows the symbol but it no longer trades
+    DelistedSymbol(String),
+    /// Fewer bars than the indicators need
+    InsufficientData { needed: usize, have: usize },
+    /// Request or settings values that cannot be used
+    InvalidSettings(String),
+    /// Fetched bars failed sanity validation
+    InvalidData(String),
+    /// The provider cannot serve this kind of request (interval, quotes, ...)
+    Unsupported(String),
+    /// Persistent store failure
+    Storage(String),
+    /// A batch task panicked or was aborted
+    TaskPanic(String),
+}
+
+/// Stable error codes. `API_RATE_LIMIT` keeps the marker older frontends
+/// search for in error text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    #[serde(rename = "API_RATE_LIMIT")]
+    RateLimited,
+    CircuitOpen,
+    HttpError,
+    NetworkError,
+    ParseError,
+    NotFound,
+    InvalidSymbol,
+    DelistedSymbol,
+    InsufficientData,
+    InvalidSettings,
+    InvalidData,
+    Unsupported,
+    StorageError,
+    TaskPanic,
+}
+
+impl AppError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::RateLimited { .. } => ErrorCode::RateLimited,
+            AppError::CircuitOpen { .. } => ErrorCode::CircuitOpen,
+            AppError::Http { .. } => ErrorCode::HttpError,
+            AppError::Network(_) => ErrorCode::NetworkError,
+            AppError::Parse(_) => ErrorCode::ParseError,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::InvalidSymbol(_) => ErrorCode::InvalidSymbol,
+            AppError::DelistedSymbol(_) => ErrorCode::DelistedSymbol,
+            AppError::InsufficientData { .. } => ErrorCode::InsufficientData,
+            AppError::InvalidSettings(_) => ErrorCode::InvalidSettings,
+            AppError::InvalidData(_) => ErrorCode::InvalidData,
+            AppError::Unsupported(_) => ErrorCode::Unsupported,
+            AppError::Storage(_) => ErrorCode::StorageError,
+            AppError::TaskPanic(_) => ErrorCode::TaskPanic,
+        }
+    }
+
+    /// Failures that may succeed if the same request is sent again later
+    pub fn is_transient(&self) -> bool {
+        match self {
+            AppError::RateLimited { .. } | AppError::CircuitOpen { .. } | AppError::Network(_) => true,
+            AppError::Http { status } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Seconds the upstream asked us to wait, if it said
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            AppError::RateLimited { retry_after } => *retry_after,
+            AppError::CircuitOpen { retry_after, .. } => Some(*retry_after),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::RateLimited { retry_after: Some(secs) } => {
+                write!(f, "API_RATE_LIMIT: rate limited, retry after {}s", secs)
+            }
+            AppError::RateLimited { retry_after: None } => write!(f, "API_RATE_LIMIT: rate limited"),
+            AppError::CircuitOpen { host, retry_after } => {
+                write!(f, "{} is throttled, retrying in {}s", host, retry_after)
+            }
+            AppError::Http { status } => write!(f, "API error: HTTP {}", status),
+            AppError::Network(e) => write!(f, "Network error: {}", e),
+            AppError::Parse(e) => write!(f, "Parse error: {}", e),
+            AppError::NotFound(e) => write!(f, "Not found: {}", e),
+            AppError::InvalidSymbol(e) => write!(f, "Invalid ticker {}", e),
+            AppError::DelistedSymbol(e) => write!(f, "Delisted: {}", e),
+            AppError::InsufficientData { needed, have } => {
+                write!(f, "Not enough data (needs {}, have {})", needed, have)
+            }
+            AppError::InvalidSettings(e) => write!(f, "Invalid settings: {}", e),
+            AppError::InvalidData(e) => write!(f, "Malformed data: {}", e),
+            AppError::Unsupported(e) => write!(f, "{}", e),
+            AppError::Storage(e) => write!(f, "Store error: {}", e),
+            AppError::TaskPanic(e) => write!(f, "Task panic: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<crate::models::InvalidTimestamp> for AppError {
+    fn from(e: crate::models::InvalidTimestamp) -> Self {
+        AppError::Parse(e.to_string())
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_decode() {
+            AppError::Parse(e.to_string())
+        } else {
+            AppError::Network(e.to_string())
+        }
+    }
+}
+
+impl From<tokio::task::JoinError> for AppError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        AppError::TaskPanic(e.to_string())
+    }
+}
+
+/// Flat wire format of `AppError`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorPayload {
+    code: ErrorCode,
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry_after: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    needed: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    have: Option<usize>,
+    /// Variant text without the display prefix (round-trips tuple variants)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+impl From<AppError> for ErrorPayload {
+    fn from(e: AppError) -> Self {
+        let mut payload = ErrorPayload {
+            code: e.code(),
+            message: e.to_string(),
+            retry_after: None,
+            status: None,
+            needed: None,
+            have: None,
+            detail: None,
+        };
+        match e {
+            AppError::RateLimited { retry_after } => payload.retry_after = retry_after,
+            AppError::CircuitOpen { host, retry_after } => {
+                payload.retry_after = Some(retry_after);
+                payload.detail = Some(host);
+            }
+            AppError::Http { status } => payload.status = Some(status),
+            AppError::InsufficientData { needed, have } => {
+                payload.needed = Some(needed);
+                payload.have = Some(have);
+            }
+            AppError::Network(d)
+            | AppError::Parse(d)
+            | AppError::NotFound(d)
+            | AppError::InvalidSymbol(d)
+            | AppError::DelistedSymbol(d)
+            | AppError::InvalidSettings(d)
+            | AppError::InvalidData(d)
+            | AppError::Unsupported(d)
+            | AppError::Storage(d)
+            | AppError::TaskPanic(d) => payload.detail = Some(d),
+        }
+        payload
+    }
+}
+
+impl From<ErrorPayload> for AppError {
+    fn from(p: ErrorPayload) -> Self {
+        let detail = p.detail.unwrap_or(p.message);
+        match p.code {
+            ErrorCode::RateLimited => AppError::RateLimited { retry_after: p.retry_after },
+            ErrorCode::CircuitOpen => AppError::CircuitOpen {
+                host: detail,
+                retry_after: p.retry_after.unwrap_or(0),
+            },
+            ErrorCode::HttpError => AppError::Http { status: p.status.unwrap_or(0) },
+            ErrorCode::NetworkError => AppError::Network(detail),
+            ErrorCode::ParseError => AppError::Parse(detail),
+            ErrorCode::NotFound => AppError::NotFound(detail),
+            ErrorCode::InvalidSymbol => AppError::InvalidSymbol(detail),
+            ErrorCode::DelistedSymbol => AppError::DelistedSymbol(detail),
+            ErrorCode::InsufficientData => AppError::InsufficientData {
+                needed: p.needed.unwrap_or(0),
+                have: p.have.unwrap_or(0),
+            },
+            ErrorCode::InvalidSettings => AppError::InvalidSettings(detail),
+            ErrorCode::InvalidData => AppError::InvalidData(detail),
+            ErrorCode::Unsupported => AppError::Unsupported(detail),
+            ErrorCode::StorageError => AppError::Storage(detail),
+            ErrorCode::TaskPanic => AppError::TaskPanic(detail),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::error::AppError;
+use crate::providers::DataProvider;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// ==========================================
+// FX Rates (cached)
+// ==========================================
+
+/// Rates move little over one analysis run; one quote per pair is plenty
+const FX_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Currencies Yahoo quotes in minor units: (code, major code, major per minor)
+const MINOR_UNITS: &[(&str, &str, f64)] = &[
+    ("GBp", "GBP", 0.01),
+    ("GBX", "GBP", 0.01),
+    ("ZAc", "ZAR", 0.01),
+    ("ILA", "ILS", 0.01),
+];
+
+/// `GBp` -> (`GBP`, 0.01); anything else is uppercased with factor 1
+fn major_unit(currency: &str) -> (String, f64) {
+    match MINOR_UNITS.iter().find(|(minor, _, _)| *minor == currency) {
+        Some((_, major, factor)) => (major.to_string(), *factor),
+        None => (currency.trim().to_uppercase(), 1.0),
+    }
+}
+
+type SharedRate = Shared<BoxFuture<'static, Result<f64, AppError>>>;
+
+/// Exchange rates quoted through the provider layer as `{FROM}{TO}=X`
+#[derive(Default)]
+pub struct FxRates {
+    /// Pair symbol -> (rate, fetched at). Never locked across a fetch, so a
+    /// slow quote for one pair does not hold up the others.
+    rates: Arc<Mutex<HashMap<String, (f64, Instant)>>>,
+    /// Pair quotes currently running; a batch converting many tickers from
+    /// the same currency awaits one request
+    inflight: Arc<Mutex<HashMap<String, SharedRate>>>,
+}
+
+/// Removes the pair's in-flight entry when its fetch task ends (even on panic)
+struct InflightGuard {
+    inflight: Arc<Mutex<HashMap<String, SharedRate>>>,
+    pair: String,
+}
+
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        if let Ok(mut inflight) = self.inflight.lock() {
+            inflight.remove(&self.pair);
+        }
+    }
+}
+
+impl FxRates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Multiply an amount in `from` by this to get `to`
+    pub async fn rate(&self, provider: Arc<dyn DataProvider>, from: &str, to: &str) -> Result<f64, AppError> {
+        let (from, from_factor) = major_unit(from);
+        let (to, to_factor) = major_unit(to);
+        if from == to {
+            return Ok(from_factor / to_factor);
+        }
+
+        let pair = format!("{}{}=X", from, to);
+        let fetch = {
+            // Checked under the in-flight lock so a fetch finishing right now
+            // is either still joinable or already cached
+            let mut inflight = self.inflight.lock().unwrap();
+            if let Some(rate) = self.cached(&pair) {
+                return Ok(rate * from_factor / to_factor);
+            }
+            match inflight.get(&pair) {
+                Some(fetch) => fetch.clone(),
+                None => {
+                    let fetch = self.spawn_fetch(provider, pair.clone());
+                    inflight.insert(pair, fetch.clone());
+                    fetch
+                }
+            }
+        };
+
+        Ok(fetch.await? * from_factor / to_factor)
+    }
+
+    fn cached(&self, pair: &str) -> Option<f64> {
+        let rates = self.rates.lock().unwrap();
+        rates.get(pair).filter(|(_, fetched)| fetched.elapsed() < FX_TTL).map(|(rate, _)| *rate)
+    }
+
+    /// Quote `pair` on its own task so a cancelled caller cannot strand the others
+    fn spawn_fetch(&self, provider: Arc<dyn DataProvider>, pair: String) -> SharedRate {
+        let rates = self.rates.clone();
+        let guard = InflightGuard { inflight: self.inflight.clone(), pair: pair.clone() };
+
+        tokio::spawn(async move {
+            let _guard = guard;
+            let quote = provider.fetch_quote(&pair).await?;
+            if !quote.price.is_finite() || quote.price <= 0.0 {
+                return Err(AppError::InvalidData(format!("{} quoted at {}", pair, quote.price)));
+            }
+            println!("[Rust] FX {} = {}", pair, quote.price);
+            rates.lock().unwrap().insert(pair, (quote.price, Instant::now()));
+            Ok(quote.price)
+        })
+        .map(|joined| joined.unwrap_or_else(|e| Err(AppError::from(e))))
+        .boxed()
+        .shared()
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tokio::sync::watch;
+use tokio::task::AbortHandle;
+
+// ==========================================
+// Batch Jobs (cancellation)
+// ==========================================
+
+struct Job {
+    cancelled: watch::Sender<bool>,
+    tasks: Vec<AbortHandle>,
+}
+
+impl Job {
+    fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+}
+
+/// Running batch commands, keyed by the job id handed to the frontend
+#[derive(Default)]
+pub struct JobRegistry {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<String, Job>>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new job and return its id
+    pub fn create(&self) -> String {
+        let id = format!("job-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let job = Job {
+            cancelled: watch::channel(false).0,
+            tasks: Vec::new(),
+        };
+        self.jobs.lock().unwrap().insert(id.clone(), job);
+        id
+    }
+
+    /// Attach a spawned task to `job_id`; aborted right away if the job was
+    /// cancelled before the task was spawned
+    pub fn track(&self, job_id: &str, task: AbortHandle) {
+        let mut jobs = self.jobs.lock().unwrap();
+        match jobs.get_mut(job_id) {
+            Some(job) if !job.is_cancelled() => job.tasks.push(task),
+            _ => task.abort(),
+        }
+    }
+
+    /// Abort every task of `job_id` that has not finished yet.
+    /// Returns false if no such job is running.
+    pub fn cancel(&self, job_id: &str) -> bool {
+        let mut jobs = self.jobs.lock().unwrap();
+        let Some(job) = jobs.get_mut(job_id) else {
+            return false;
+        };
+        job.cancelled.send_replace(true);
+        for task in job.tasks.drain(..) {
+            task.abort();
+        }
+        true
+    }
+
+    pub fn is_cancelled(&self, job_id: &str) -> bool {
+        self.jobs.lock().unwrap().get(job_id).is_some_and(Job::is_cancelled)
+    }
+
+    /// Resolves once `job_id` is cancelled (never, for unknown jobs)
+    pub async fn cancelled(&self, job_id: &str) {
+        let receiver = self.jobs.lock().unwrap().get(job_id).map(|job| job.cancelled.subscribe());
+        match receiver {
+            Some(mut receiver) => {
+                // Err means the job finished; nothing left to cancel
+                if receiver.wait_for(|cancelled| *cancelled).await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Forget a job once all its tasks are done
+    pub fn finish(&self, job_id: &str) {
+        self.jobs.lock().unwrap().remove(job_id);
+    }
+}
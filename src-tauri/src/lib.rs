@@ -1,18 +1,90 @@
 pub mod models;
 pub mod analysis;
+pub mod breaker;
+pub mod cache;
 pub mod commands;
+pub mod concurrency;
+pub mod error;
+pub mod fx;
+pub mod jobs;
+pub mod providers;
+pub mod quality;
+pub mod ratelimit;
+pub mod store;
+pub mod symbol;
 
 use crate::commands::{stock, market, window};
 
+use futures::future::{BoxFuture, Shared};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use std::time::Instant;
+use crate::breaker::CircuitBreaker;
+use crate::cache::HistoryCache;
+use crate::error::AppError;
+use crate::fx::FxRates;
+use crate::jobs::JobRegistry;
 use crate::models::HistoricalData;
+use crate::providers::{DataProvider, LocalFileProvider, ProviderChain, StooqProvider, YahooProvider};
+use crate::providers::stooq::STOOQ_HOST;
+use crate::providers::yahoo::{YAHOO_QUERY1_HOST, YAHOO_QUERY2_HOST};
+use crate::ratelimit::{HostLimit, RateLimiter};
+use crate::store::HistoryStore;
 
+#[derive(Clone)]
 pub struct AppState {
+    /// For emitting events from background work
+    pub app: tauri::AppHandle,
     pub client: reqwest::Client,
-    pub cache: Arc<RwLock<HashMap<String, (HistoricalData, Instant)>>>,
+    pub provider: Arc<dyn DataProvider>,
+    pub local_data: Arc<LocalFileProvider>,
+    /// Shared per-host request budget for every provider call
+    pub limiter: Arc<RateLimiter>,
+    /// Per-host failure tracking; open circuits fail fast
+    pub breaker: Arc<CircuitBreaker>,
+    /// Persistent bar store; `None` if the database could not be opened
+    pub store: Option<Arc<HistoryStore>>,
+    pub cache: Arc<RwLock<HistoryCache>>,
+    /// Fetches currently running, keyed like `cache`; concurrent callers await the same one
+    pub inflight: Arc<std::sync::Mutex<HashMap<String, SharedFetch>>>,
+    /// Running batch commands, for `cancel_job`
+    pub jobs: Arc<JobRegistry>,
+    /// Cached FX rates for base-currency conversion
+    pub fx: Arc<FxRates>,
+}
+
+pub type SharedFetch = Shared<BoxFuture<'static, Result<HistoricalData, AppError>>>;
+
+/// Failover order: Yahoo query1 -> Yahoo query2 -> Stooq CSV -> local files (when configured).
+/// Offline mode bypasses the chain and reads local files only.
+fn build_provider(
+    client: &reqwest::Client,
+    local_data: Arc<LocalFileProvider>,
+    limiter: Arc<RateLimiter>,
+    breaker: Arc<CircuitBreaker>,
+) -> Arc<dyn DataProvider> {
+    Arc::new(ProviderChain::new(vec![
+        Arc::new(YahooProvider::new(client.clone(), limiter.clone(), breaker.clone())),
+        Arc::new(YahooProvider::with_host(
+            client.clone(),
+            limiter.clone(),
+            breaker.clone(),
+            "yahoo-query2",
+            YAHOO_QUERY2_HOST,
+        )),
+        Arc::new(StooqProvider::new(client.clone(), limiter, breaker)),
+        local_data,
+    ]))
+}
+
+/// Conservative budgets: Yahoo starts answering 429 at a few requests per second
+fn build_limiter() -> Arc<RateLimiter> {
+    let yahoo = HostLimit { burst: 4.0, per_second: 2.0 };
+    Arc::new(RateLimiter::new(HashMap::from([
+        (YAHOO_QUERY1_HOST.to_string(), yahoo),
+        (YAHOO_QUERY2_HOST.to_string(), yahoo),
+        (STOOQ_HOST.to_string(), HostLimit { burst: 2.0, per_second: 1.0 }),
+    ])))
 }
 
 pub fn run() {
@@ -22,14 +94,44 @@ pub fn run() {
         .build()
         .expect("Failed to create reqwest client");
 
-    let cache = Arc::new(RwLock::new(HashMap::new()));
+    let local_data = Arc::new(LocalFileProvider::from_env());
+    let limiter = build_limiter();
+    let breaker = Arc::new(CircuitBreaker::new());
+    let provider = build_provider(&client, local_data.clone(), limiter.clone(), breaker.clone());
+    let cache = Arc::new(RwLock::new(HistoryCache::new()));
+    let inflight = Arc::new(std::sync::Mutex::new(HashMap::new()));
+    let jobs = Arc::new(JobRegistry::new());
+    let fx = Arc::new(FxRates::new());
     
     tauri::Builder::default()
-        .manage(AppState { client, cache })
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_http::init())
-        .setup(|app| {
+        .setup(move |app| {
+            use tauri::Manager;
+
+            // The store lives in the app data dir, which is only known once the app is built
+            let store = app.path().app_data_dir()
+                .map_err(|e| crate::error::AppError::Storage(e.to_string()))
+                .and_then(|dir| HistoryStore::open(&dir.join("history.sqlite3")))
+                .map(Arc::new)
+                .map_err(|e| println!("[Rust] History store disabled: {}", e))
+                .ok();
+
+            app.manage(AppState {
+                app: app.handle().clone(),
+                client,
+                provider,
+                local_data,
+                limiter,
+                breaker,
+                store,
+                cache,
+                inflight,
+                jobs,
+                fx,
+            });
+
             #[cfg(target_os = "windows")]
             {
                 use tauri::Manager;
@@ -61,6 +163,16 @@ pub fn run() {
             stock::fetch_multiple_stocks,
             stock::analyze_stock,
             stock::analyze_multiple_stocks,
+            stock::cancel_job,
+            stock::fetch_quotes,
+            stock::search_symbols,
+            stock::validate_symbol,
+            stock::get_local_data_config,
+            stock::set_local_data_config,
+            stock::get_upstream_status,
+            stock::get_cache_stats,
+            stock::invalidate_cache,
+            stock::clear_cache,
             market::fetch_market_indicators,
             window::set_always_on_top,
             window::set_shadow,
@@ -1,18 +1,737 @@
+use crate::error::AppError;
 use serde::{Deserialize, Serialize};
 
 // ==========================================
 // Yahoo Finance Data Structures
 // ==========================================
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct HistoricalData {
-    pub dates: Vec<String>,
+    /// Bar start time in exchange-local offset, serialized as RFC 3339
+    /// (`2024-01-02T09:30:00-05:00`). Date-only sources use local midnight.
+    pub dates: Vec<chrono::DateTime<chrono::FixedOffset>>,
     pub opens: Vec<f64>,
     pub highs: Vec<f64>,
     pub lows: Vec<f64>,
     pub closes: Vec<f64>,
     pub adj_closes: Vec<f64>,
     pub volumes: Vec<u64>,
+    /// Provider that served this series (e.g. "yahoo", "stooq")
+    #[serde(default)]
+    pub source: String,
+    /// IANA name of the exchange timezone (e.g. "America/New_York"), when known
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Exchange offset from UTC in seconds at fetch time, when known
+    #[serde(default)]
+    pub gmtoffset: Option<i32>,
+    /// Trading session per bar for intraday series; empty for daily bars
+    #[serde(default)]
+    pub sessions: Vec<BarSession>,
+    /// Per-bar flag for bars the provider returned without prices (values are
+    /// NaN, serialized as null). Empty when no bar is missing.
+    #[serde(default)]
+    pub missing: Vec<bool>,
+    /// Set once the gap policy has been applied
+    #[serde(default)]
+    pub quality: Option<DataQualityReport>,
+    /// Cash dividends by ex-date, oldest first
+    #[serde(default)]
+    pub dividends: Vec<Dividend>,
+    /// Stock splits by effective date, oldest first
+    #[serde(default)]
+    pub splits: Vec<Split>,
+    /// Currency prices are quoted in (`USD`, `KRW`, `GBp`), when the provider says
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+/// Cash dividend; `date` is the ex-dividend date
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Dividend {
+    pub date: chrono::DateTime<chrono::FixedOffset>,
+    pub amount: f64,
+}
+
+/// Stock split, e.g. 4-for-1 is `numerator: 4, denominator: 1`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Split {
+    pub date: chrono::DateTime<chrono::FixedOffset>,
+    pub numerator: f64,
+    pub denominator: f64,
+}
+
+/// Which part of the trading day an intraday bar belongs to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BarSession {
+    Pre,
+    Regular,
+    Post,
+}
+
+/// A bar timestamp that cannot be represented (out of chrono's range)
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidTimestamp {
+    pub index: usize,
+    pub timestamp: i64,
+}
+
+impl std::fmt::Display for InvalidTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid timestamp {} at bar {}", self.timestamp, self.index)
+    }
+}
+
+impl std::error::Error for InvalidTimestamp {}
+
+/// Bar time for date-only sources (CSV files, Stooq): midnight, offset unknown
+pub fn daily_bar_time(day: chrono::NaiveDate) -> chrono::DateTime<chrono::FixedOffset> {
+    day.and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        .fixed_offset()
+}
+
+impl HistoricalData {
+    /// Exchange-local trading day of bar `i`
+    pub fn trading_day(&self, i: usize) -> Option<chrono::NaiveDate> {
+        self.dates.get(i).map(|d| d.date_naive())
+    }
+
+    /// Keep only bars whose trading day is within `[start, end]` (inclusive)
+    pub fn slice_dates(&self, start: Option<chrono::NaiveDate>, end: Option<chrono::NaiveDate>) -> HistoricalData {
+        let from = start
+            .map(|s| self.dates.iter().position(|d| d.date_naive() >= s).unwrap_or(self.dates.len()))
+            .unwrap_or(0);
+        let to = end
+            .map(|e| self.dates.iter().rposition(|d| d.date_naive() <= e).map_or(0, |i| i + 1))
+            .unwrap_or(self.dates.len())
+            .max(from);
+
+        HistoricalData {
+            dates: self.dates[from..to].to_vec(),
+            opens: self.opens.get(from..to).unwrap_or_default().to_vec(),
+            highs: self.highs.get(from..to).unwrap_or_default().to_vec(),
+            lows: self.lows.get(from..to).unwrap_or_default().to_vec(),
+            closes: self.closes.get(from..to).unwrap_or_default().to_vec(),
+            adj_closes: self.adj_closes.get(from..to).unwrap_or_default().to_vec(),
+            volumes: self.volumes.get(from..to).unwrap_or_default().to_vec(),
+            source: self.source.clone(),
+            timezone: self.timezone.clone(),
+            gmtoffset: self.gmtoffset,
+            currency: self.currency.clone(),
+            sessions: self.sessions.get(from..to).unwrap_or_default().to_vec(),
+            missing: self.missing.get(from..to).unwrap_or_default().to_vec(),
+            quality: self.quality.clone(),
+            // Events are kept by date so announced future ex-dates survive
+            // an open-ended window
+            dividends: self.dividends.iter()
+                .filter(|d| in_window(d.date.date_naive(), start, end))
+                .cloned()
+                .collect(),
+            splits: self.splits.iter()
+                .filter(|s| in_window(s.date.date_naive(), start, end))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// True when every per-bar series has the same length as `dates`
+    pub fn lengths_match(&self) -> bool {
+        let n = self.dates.len();
+        [
+            self.opens.len(),
+            self.highs.len(),
+            self.lows.len(),
+            self.closes.len(),
+            self.adj_closes.len(),
+            self.volumes.len(),
+        ]
+        .iter()
+        .all(|&len| len == n)
+    }
+
+    pub fn is_missing(&self, i: usize) -> bool {
+        self.missing.get(i).copied().unwrap_or(false)
+    }
+
+    /// Copy of the bars at the given indices (in order)
+    pub fn select(&self, keep: &[usize]) -> HistoricalData {
+        let pick = |v: &[f64]| keep.iter().filter_map(|&i| v.get(i).copied()).collect::<Vec<f64>>();
+
+        HistoricalData {
+            dates: keep.iter().filter_map(|&i| self.dates.get(i).copied()).collect(),
+            opens: pick(&self.opens),
+            highs: pick(&self.highs),
+            lows: pick(&self.lows),
+            closes: pick(&self.closes),
+            adj_closes: pick(&self.adj_closes),
+            volumes: keep.iter().filter_map(|&i| self.volumes.get(i).copied()).collect(),
+            source: self.source.clone(),
+            timezone: self.timezone.clone(),
+            gmtoffset: self.gmtoffset,
+            currency: self.currency.clone(),
+            sessions: keep.iter().filter_map(|&i| self.sessions.get(i).copied()).collect(),
+            missing: if self.missing.is_empty() {
+                Vec::new()
+            } else {
+                keep.iter().map(|&i| self.is_missing(i)).collect()
+            },
+            quality: self.quality.clone(),
+            dividends: self.dividends.clone(),
+            splits: self.splits.clone(),
+        }
+    }
+
+    /// Drop pre/post-market bars (no-op for series without session info)
+    pub fn regular_session_only(&self) -> HistoricalData {
+        if self.sessions.is_empty() {
+            return self.clone();
+        }
+
+        let keep: Vec<usize> = (0..self.dates.len())
+            .filter(|&i| self.sessions.get(i) == Some(&BarSession::Regular))
+            .collect();
+        self.select(&keep)
+    }
+
+    /// Only bars with real prices (indicators must never see NaN)
+    pub fn without_missing(&self) -> HistoricalData {
+        if self.missing.is_empty() {
+            return self.clone();
+        }
+
+        let keep: Vec<usize> = (0..self.dates.len()).filter(|&i| !self.is_missing(i)).collect();
+        self.select(&keep)
+    }
+
+    /// True when a split took effect on the trading day of bar `i`
+    pub fn is_split_day(&self, i: usize) -> bool {
+        self.trading_day(i)
+            .is_some_and(|day| self.splits.iter().any(|s| s.date.date_naive() == day))
+    }
+
+    /// Recompute `adj_closes` from `closes` and the dividend list.
+    ///
+    /// Closes are expected to be split-adjusted already (as Yahoo returns
+    /// them). Each dividend scales every bar before its ex-date by
+    /// `1 - amount / previous close`, the same method Yahoo uses.
+    pub fn rebuild_adj_closes(&mut self) {
+        let len = self.dates.len().min(self.closes.len());
+        let mut factors = vec![1.0; len];
+
+        for dividend in &self.dividends {
+            let ex_day = dividend.date.date_naive();
+            let Some(ex_idx) = (0..len).find(|&i| self.dates[i].date_naive() >= ex_day) else {
+                continue;
+            };
+            let Some(&prev_close) = ex_idx.checked_sub(1).and_then(|i| self.closes.get(i)) else {
+                continue;
+            };
+            // Also skips NaN closes of missing bars
+            if !(prev_close > dividend.amount && dividend.amount > 0.0) {
+                continue;
+            }
+            let factor = 1.0 - dividend.amount / prev_close;
+            for f in &mut factors[..ex_idx] {
+                *f *= factor;
+            }
+        }
+
+        self.adj_closes = (0..len).map(|i| self.closes[i] * factors[i]).collect();
+    }
+}
+
+fn in_window(day: chrono::NaiveDate, start: Option<chrono::NaiveDate>, end: Option<chrono::NaiveDate>) -> bool {
+    start.map_or(true, |s| day >= s) && end.map_or(true, |e| day <= e)
+}
+
+// ==========================================
+// Missing Bars & Data Quality
+// ==========================================
+
+/// What to do with bars the provider returned without prices
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum GapPolicy {
+    /// Remove the bar from every series
+    #[default]
+    Drop,
+    /// Flat bar at the previous close with zero volume
+    ForwardFill,
+    /// Keep the bar flagged in `missing` (values NaN); analysis skips it
+    MarkMissing,
+}
+
+impl GapPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GapPolicy::Drop => "drop",
+            GapPolicy::ForwardFill => "forwardFill",
+            GapPolicy::MarkMissing => "markMissing",
+        }
+    }
+}
+
+/// Per-ticker summary of how complete the fetched series was
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DataQualityReport {
+    pub gap_policy: GapPolicy,
+    /// Bars in the series after the policy was applied
+    pub bars: usize,
+    /// Bars the provider returned with null prices
+    pub null_bars: usize,
+    pub dropped_bars: usize,
+    pub filled_bars: usize,
+    /// Daily series: weekdays between first and last bar with no bar at all
+    /// (weekday calendar, so exchange holidays are included)
+    pub calendar_gaps: usize,
+    /// Bars with real prices but zero volume
+    pub zero_volume_bars: usize,
+    /// Sanity-check findings; any `Error` makes the series unusable for analysis
+    #[serde(default)]
+    pub issues: Vec<ValidationIssue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueKind {
+    LengthMismatch,
+    HighBelowLow,
+    CloseOutsideRange,
+    NonPositivePrice,
+    SuspiciousJump,
+    StaleData,
+}
+
+/// One class of problem found in a series (bars are aggregated per kind)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub kind: IssueKind,
+    pub severity: IssueSeverity,
+    /// Number of affected bars
+    pub count: usize,
+    pub first_date: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub message: String,
+}
+
+// ==========================================
+// History Range & Interval
+// ==========================================
+
+/// Bar size (Yahoo interval vocabulary)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Interval {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "2m")]
+    TwoMinutes,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "15m")]
+    FifteenMinutes,
+    #[serde(rename = "30m")]
+    ThirtyMinutes,
+    #[serde(rename = "60m", alias = "1h")]
+    OneHour,
+    #[serde(rename = "90m")]
+    NinetyMinutes,
+    #[default]
+    #[serde(rename = "1d")]
+    OneDay,
+    #[serde(rename = "1wk")]
+    OneWeek,
+    #[serde(rename = "1mo")]
+    OneMonth,
+}
+
+impl Interval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::OneMinute => "1m",
+            Interval::TwoMinutes => "2m",
+            Interval::FiveMinutes => "5m",
+            Interval::FifteenMinutes => "15m",
+            Interval::ThirtyMinutes => "30m",
+            Interval::OneHour => "60m",
+            Interval::NinetyMinutes => "90m",
+            Interval::OneDay => "1d",
+            Interval::OneWeek => "1wk",
+            Interval::OneMonth => "1mo",
+        }
+    }
+
+    pub fn is_intraday(&self) -> bool {
+        !matches!(self, Interval::OneDay | Interval::OneWeek | Interval::OneMonth)
+    }
+
+    /// Oldest data Yahoo serves for this bar size, in days
+    pub fn max_lookback_days(&self) -> Option<i64> {
+        match self {
+            Interval::OneMinute => Some(7),
+            Interval::OneHour => Some(730),
+            i if i.is_intraday() => Some(60),
+            _ => None,
+        }
+    }
+}
+
+/// Lookback period (Yahoo range vocabulary)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HistoryRange {
+    #[serde(rename = "1d")]
+    OneDay,
+    #[serde(rename = "5d")]
+    FiveDays,
+    #[serde(rename = "1mo")]
+    OneMonth,
+    #[serde(rename = "3mo")]
+    ThreeMonths,
+    #[default]
+    #[serde(rename = "6mo")]
+    SixMonths,
+    #[serde(rename = "1y")]
+    OneYear,
+    #[serde(rename = "2y")]
+    TwoYears,
+    #[serde(rename = "5y")]
+    FiveYears,
+    #[serde(rename = "10y")]
+    TenYears,
+    #[serde(rename = "ytd")]
+    YearToDate,
+    #[serde(rename = "max")]
+    Max,
+}
+
+impl HistoryRange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryRange::OneDay => "1d",
+            HistoryRange::FiveDays => "5d",
+            HistoryRange::OneMonth => "1mo",
+            HistoryRange::ThreeMonths => "3mo",
+            HistoryRange::SixMonths => "6mo",
+            HistoryRange::OneYear => "1y",
+            HistoryRange::TwoYears => "2y",
+            HistoryRange::FiveYears => "5y",
+            HistoryRange::TenYears => "10y",
+            HistoryRange::YearToDate => "ytd",
+            HistoryRange::Max => "max",
+        }
+    }
+
+    /// First calendar day covered when counting back from `today` (`None` = all history)
+    pub fn start_date(&self, today: chrono::NaiveDate) -> Option<chrono::NaiveDate> {
+        use chrono::{Datelike, Days, Months};
+        match self {
+            HistoryRange::OneDay => today.checked_sub_days(Days::new(1)),
+            HistoryRange::FiveDays => today.checked_sub_days(Days::new(5)),
+            HistoryRange::OneMonth => today.checked_sub_months(Months::new(1)),
+            HistoryRange::ThreeMonths => today.checked_sub_months(Months::new(3)),
+            HistoryRange::SixMonths => today.checked_sub_months(Months::new(6)),
+            HistoryRange::OneYear => today.checked_sub_months(Months::new(12)),
+            HistoryRange::TwoYears => today.checked_sub_months(Months::new(24)),
+            HistoryRange::FiveYears => today.checked_sub_months(Months::new(60)),
+            HistoryRange::TenYears => today.checked_sub_months(Months::new(120)),
+            HistoryRange::YearToDate => chrono::NaiveDate::from_ymd_opt(today.year(), 1, 1),
+            HistoryRange::Max => None,
+        }
+    }
+}
+
+/// Which bars to fetch: a lookback `range`, or an explicit `start` date
+/// (which takes precedence), optionally up to `end`, at the given `interval`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryRequest {
+    pub range: HistoryRange,
+    pub start: Option<chrono::NaiveDate>,
+    pub end: Option<chrono::NaiveDate>,
+    pub interval: Interval,
+    /// Intraday only: include pre/post-market bars
+    pub include_extended_hours: bool,
+    pub gap_policy: GapPolicy,
+    /// Return an expired cached series immediately and refresh it in the
+    /// background (a `history-updated` event follows). Not part of the cache key.
+    pub allow_stale: bool,
+}
+
+impl HistoryRequest {
+    pub fn validate(&self) -> Result<(), AppError> {
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if start > end {
+                return Err(AppError::InvalidSettings(format!(
+                    "start {} is after end {}",
+                    start, end
+                )));
+            }
+        }
+
+        if let Some(max_days) = self.interval.max_lookback_days() {
+            let today = chrono::Utc::now().date_naive();
+            let oldest = today - chrono::Duration::days(max_days);
+            let window_start = self.window_start(today).unwrap_or(chrono::NaiveDate::MIN);
+            if window_start < oldest {
+                return Err(AppError::InvalidSettings(format!(
+                    "interval {} only supports the last {} days",
+                    self.interval.as_str(),
+                    max_days
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// First day of the requested window (`None` = from inception).
+    /// A range counts back from `end` when one is given.
+    pub fn window_start(&self, today: chrono::NaiveDate) -> Option<chrono::NaiveDate> {
+        match self.start {
+            Some(start) => Some(start),
+            None => self.range.start_date(self.end.unwrap_or(today)),
+        }
+    }
+
+    /// Cache key component: everything that changes the returned bars
+    pub fn cache_key(&self) -> String {
+        let extended = if self.include_extended_hours && self.interval.is_intraday() { "+ext" } else { "" };
+        let window = match self.start {
+            Some(start) => format!(
+                "{}:{}..{}",
+                self.interval.as_str(),
+                start,
+                self.end.map(|e| e.to_string()).unwrap_or_default()
+            ),
+            None => format!(
+                "{}:{}{}",
+                self.interval.as_str(),
+                self.range.as_str(),
+                self.end.map(|e| format!("..{}", e)).unwrap_or_default()
+            ),
+        };
+        format!("{}{}:{}", window, extended, self.gap_policy.as_str())
+    }
+}
+
+/// Payload of the `history-updated` event sent after a background refresh
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryUpdatedEvent {
+    pub ticker: String,
+    pub request: HistoryRequest,
+    pub data: HistoricalData,
+}
+
+// ==========================================
+// Batch Job Events
+// ==========================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TickerStatus {
+    Done,
+    Failed,
+    /// Failed transiently; queued for another attempt after a cool-down
+    Retrying,
+    Cancelled,
+}
+
+/// Streamed over the batch command's channel: `started`, one `ticker`
+/// per ticker as its task finishes (in completion order, plus a `retrying`
+/// one before each retry), then `finished`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum BatchEvent<T> {
+    Started(BatchStarted),
+    Ticker(TickerEvent<T>),
+    Finished(BatchFinished),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStarted {
+    pub job_id: String,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TickerEvent<T> {
+    pub job_id: String,
+    pub ticker: String,
+    /// Position of the ticker in the request (events arrive in completion order)
+    pub index: usize,
+    pub status: TickerStatus,
+    /// 1 for the first pass, 2 for the retry pass
+    pub attempt: u32,
+    /// Absent for failed fetches and cancelled tickers
+    pub result: Option<T>,
+    pub error: Option<AppError>,
+    pub timing: TickerTiming,
+    /// Tickers with a final outcome so far (`retrying` does not count)
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Where one attempt at a ticker spent its time. Stages the task did not
+/// reach (panic, cancellation, failed fetch) are absent.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TickerTiming {
+    /// Waiting for a concurrency slot
+    pub queue_ms: Option<u64>,
+    /// Cache lookup plus provider requests (including rate-limit waits)
+    pub fetch_ms: Option<u64>,
+    pub analysis_ms: Option<u64>,
+    /// From spawning the task to its outcome
+    pub total_ms: u64,
+}
+
+/// Final outcome of one ticker, without the result payload
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TickerOutcome {
+    pub ticker: String,
+    pub index: usize,
+    pub status: TickerStatus,
+    pub attempts: u32,
+    pub error: Option<AppError>,
+    /// Of the last attempt
+    pub timing: TickerTiming,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchFinished {
+    pub job_id: String,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+    /// Tickers that went through the retry pass
+    pub retried: usize,
+    pub elapsed_ms: u64,
+    pub concurrency: ConcurrencySummary,
+    /// One per requested ticker, in request order
+    pub outcomes: Vec<TickerOutcome>,
+}
+
+/// How many tickers the batch ran at once, as the adaptive limit moved
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConcurrencySummary {
+    pub initial: usize,
+    pub min: usize,
+    pub max: usize,
+    /// Limit when the batch ended
+    pub last: usize,
+    /// Attempts that hit a 429 or an open circuit
+    pub throttled: usize,
+}
+
+/// Trading session at the time of a quote
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MarketState {
+    Pre,
+    Regular,
+    Post,
+    Closed,
+}
+
+/// Last price of one ticker, without any bars (`fetch_quotes`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteSnapshot {
+    pub ticker: String,
+    pub price: f64,
+    pub previous_close: Option<f64>,
+    /// `price - previous_close`
+    pub change: Option<f64>,
+    pub change_percent: Option<f64>,
+    pub day_high: Option<f64>,
+    pub day_low: Option<f64>,
+    pub volume: Option<u64>,
+    pub market_state: MarketState,
+    pub currency: Option<String>,
+    /// When `price` was last traded, in exchange time
+    pub timestamp: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub source: String,
+}
+
+/// One `fetch_quotes` entry; exactly one of `quote` / `error` is set
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteResult {
+    pub ticker: String,
+    pub quote: Option<QuoteSnapshot>,
+    pub error: Option<AppError>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AssetType {
+    Equity,
+    Etf,
+    MutualFund,
+    Index,
+    Crypto,
+    Currency,
+    Future,
+    Other,
+}
+
+impl AssetType {
+    /// Yahoo `quoteType` (EQUITY, ETF, CRYPTOCURRENCY, ...)
+    pub fn from_yahoo(quote_type: &str) -> Self {
+        match quote_type {
+            "EQUITY" => AssetType::Equity,
+            "ETF" => AssetType::Etf,
+            "MUTUALFUND" => AssetType::MutualFund,
+            "INDEX" => AssetType::Index,
+            "CRYPTOCURRENCY" => AssetType::Crypto,
+            "CURRENCY" => AssetType::Currency,
+            "FUTURE" => AssetType::Future,
+            _ => AssetType::Other,
+        }
+    }
+}
+
+/// One `search_symbols` hit
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolMatch {
+    /// Canonical ticker (`BRK.B`, `005930.KS`)
+    pub symbol: String,
+    pub name: Option<String>,
+    /// Exchange as the source names it (e.g. "NASDAQ", "KSE")
+    pub exchange: Option<String>,
+    pub asset_type: AssetType,
+    pub currency: Option<String>,
+    /// Provider that answered
+    pub source: String,
+}
+
+/// Folder of per-ticker OHLCV files used by the local provider
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalDataConfig {
+    pub dir: Option<String>,
+    /// When true, analysis never touches the network
+    pub offline: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,6 +750,30 @@ pub struct YahooResult {
     pub meta: YahooMeta,
     pub timestamp: Option<Vec<i64>>,
     pub indicators: YahooIndicators,
+    /// Present when `events=div,splits` was requested and any occurred
+    pub events: Option<YahooEvents>,
+}
+
+/// Corporate actions, keyed by event timestamp (as a string)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YahooEvents {
+    pub dividends: Option<std::collections::HashMap<String, YahooDividend>>,
+    pub splits: Option<std::collections::HashMap<String, YahooSplit>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YahooDividend {
+    pub amount: f64,
+    pub date: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YahooSplit {
+    pub date: i64,
+    pub numerator: f64,
+    pub denominator: f64,
+    pub split_ratio: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -40,6 +783,33 @@ pub struct YahooMeta {
     pub symbol: String,
     pub regular_market_price: Option<f64>,
     pub previous_close: Option<f64>,
+    /// Close before the chart's first bar; the previous close for `range=1d`
+    pub chart_previous_close: Option<f64>,
+    pub regular_market_day_high: Option<f64>,
+    pub regular_market_day_low: Option<f64>,
+    pub regular_market_volume: Option<u64>,
+    /// Unix time of `regular_market_price`
+    pub regular_market_time: Option<i64>,
+    pub exchange_timezone_name: Option<String>,
+    pub gmtoffset: Option<i64>,
+    pub current_trading_period: Option<YahooTradingPeriod>,
+    /// Intraday only. Either `[[period]]` (regular hours) or
+    /// `{ pre, regular, post }` when extended hours were requested.
+    pub trading_periods: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YahooTradingPeriod {
+    pub pre: Option<YahooPeriod>,
+    pub regular: Option<YahooPeriod>,
+    pub post: Option<YahooPeriod>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YahooPeriod {
+    pub start: i64,
+    pub end: i64,
+    pub gmtoffset: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -62,6 +832,49 @@ pub struct YahooAdjClose {
     pub adjclose: Vec<Option<f64>>,
 }
 
+/// `/v1/finance/search` response (only the fields we use)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YahooSearchResponse {
+    #[serde(default)]
+    pub quotes: Vec<YahooSearchQuote>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YahooSearchQuote {
+    pub symbol: String,
+    pub shortname: Option<String>,
+    pub longname: Option<String>,
+    pub exch_disp: Option<String>,
+    pub exchange: Option<String>,
+    pub quote_type: Option<String>,
+}
+
+/// `/v7/finance/spark` response: one chart per requested symbol
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct YahooSparkResponse {
+    pub spark: YahooSpark,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct YahooSpark {
+    pub result: Option<Vec<YahooSparkResult>>,
+    pub error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YahooSparkResult {
+    pub symbol: String,
+    #[serde(default)]
+    pub response: Vec<YahooSparkChart>,
+}
+
+/// Only the meta is used; the bars are a close-only sparkline
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YahooSparkChart {
+    pub meta: YahooMeta,
+}
+
 // ==========================================
 // Analysis Result Structure (Matches Client Type)
 // ==========================================
@@ -78,7 +891,53 @@ pub struct TauriAnalysisResult {
     pub bollinger_upper: f64,
     pub bollinger_middle: f64,
     pub triple_signal: bool,
-    pub error: Option<String>,
+    pub data_source: Option<String>,
+    pub data_quality: Option<DataQualityReport>,
+    /// Ex-dividend date within a few days of the last bar (before or after);
+    /// a dip around it is usually the dividend, not oversold selling
+    pub ex_dividend: Option<Dividend>,
+    /// Currency of `current_price` and the bands
+    pub currency: Option<String>,
+    /// Same prices in `AnalysisSettings.base_currency`, when requested and
+    /// the FX rate was available
+    pub converted: Option<ConvertedPrices>,
+    pub error: Option<AppError>,
+}
+
+/// Result prices converted to the base currency
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertedPrices {
+    pub currency: String,
+    /// Units of `currency` per unit of the result's currency
+    pub rate: f64,
+    pub current_price: f64,
+    pub bollinger_lower: f64,
+    pub bollinger_upper: f64,
+    pub bollinger_middle: f64,
+}
+
+impl TauriAnalysisResult {
+    /// Placeholder result for a ticker that could not be analyzed
+    pub fn failed(ticker: String, error: AppError) -> Self {
+        TauriAnalysisResult {
+            ticker,
+            current_price: 0.0,
+            rsi: 0.0,
+            mfi: 0.0,
+            bollinger_position: "inside".to_string(),
+            bollinger_lower: 0.0,
+            bollinger_upper: 0.0,
+            bollinger_middle: 0.0,
+            triple_signal: false,
+            data_source: None,
+            data_quality: None,
+            ex_dividend: None,
+            currency: None,
+            converted: None,
+            error: Some(error),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -92,6 +951,12 @@ pub struct AnalysisSettings {
     pub mfi_triple_signal: f64,
     pub bb_period: usize,
     pub bb_std_dev: f64,
+    /// Intraday: compute indicators on regular-session bars only
+    #[serde(default)]
+    pub exclude_extended_hours: bool,
+    /// Also report prices converted to this currency (e.g. "KRW")
+    #[serde(default)]
+    pub base_currency: Option<String>,
 }
 
 // ==========================================
@@ -0,0 +1,211 @@
+use super::{DataProvider, ProviderCapabilities};
+use crate::error::AppError;
+use crate::models::{HistoricalData, HistoryRequest, QuoteSnapshot, SymbolMatch};
+use crate::symbol::Symbol;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+// ==========================================
+// Ordered Provider Failover
+// ==========================================
+
+/// Tries each provider in order and returns the first success.
+///
+/// The served `HistoricalData.source` names the provider that actually
+/// answered, so results can show where the data came from.
+pub struct ProviderChain {
+    providers: Vec<Arc<dyn DataProvider>>,
+}
+
+impl ProviderChain {
+    pub fn new(providers: Vec<Arc<dyn DataProvider>>) -> Self {
+        Self { providers }
+    }
+
+    pub fn providers(&self) -> &[Arc<dyn DataProvider>] {
+        &self.providers
+    }
+}
+
+/// Pick the failure to report when every provider failed.
+///
+/// Rate limiting wins so the caller knows a retry may work; providers that
+/// simply cannot serve the request rank last. Ties go to the earlier provider.
+fn primary_error(errors: Vec<AppError>) -> AppError {
+    let rank = |e: &AppError| match e {
+        AppError::RateLimited { .. } | AppError::CircuitOpen { .. } => 0,
+        AppError::DelistedSymbol(_) => 1,
+        AppError::Http { .. } | AppError::Network(_) => 2,
+        AppError::Unsupported(_) => 4,
+        _ => 3,
+    };
+    errors
+        .into_iter()
+        .min_by_key(rank)
+        .unwrap_or_else(|| AppError::Unsupported("No data provider configured".to_string()))
+}
+
+#[async_trait]
+impl DataProvider for ProviderChain {
+    fn name(&self) -> &str {
+        "chain"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.providers.iter().fold(
+            ProviderCapabilities {
+                history: false,
+                quotes: false,
+                adjusted_close: false,
+                search: false,
+            },
+            |acc, p| {
+                let caps = p.capabilities();
+                ProviderCapabilities {
+                    history: acc.history || caps.history,
+                    quotes: acc.quotes || caps.quotes,
+                    adjusted_close: acc.adjusted_close || caps.adjusted_close,
+                    search: acc.search || caps.search,
+                }
+            },
+        )
+    }
+
+    async fn fetch_history(&self, ticker: &str, request: &HistoryRequest) -> Result<HistoricalData, AppError> {
+        let mut errors = Vec::new();
+
+        for provider in self.providers.iter().filter(|p| p.capabilities().history) {
+            match provider.fetch_history(ticker, request).await {
+                Ok(mut data) => {
+                    if data.source.is_empty() {
+                        data.source = provider.name().to_string();
+                    }
+                    if !errors.is_empty() {
+                        println!("[Rust] {} served by fallback provider: {}", ticker, provider.name());
+                    }
+                    return Ok(data);
+                }
+                Err(e) => {
+                    println!("[Rust] Provider {} failed for {}: {}", provider.name(), ticker, e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        Err(primary_error(errors))
+    }
+
+    async fn fetch_quote(&self, ticker: &str) -> Result<QuoteSnapshot, AppError> {
+        let mut errors = Vec::new();
+
+        for provider in self.providers.iter().filter(|p| p.capabilities().quotes) {
+            match provider.fetch_quote(ticker).await {
+                Ok(quote) => return Ok(quote),
+                Err(e) => {
+                    println!("[Rust] Provider {} failed quote for {}: {}", provider.name(), ticker, e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        Err(primary_error(errors))
+    }
+
+    /// Each provider is asked only for the tickers every earlier one failed
+    async fn fetch_quotes(&self, tickers: &[String]) -> Result<Vec<Result<QuoteSnapshot, AppError>>, AppError> {
+        let mut quotes: Vec<Option<QuoteSnapshot>> = vec![None; tickers.len()];
+        let mut errors: Vec<Vec<AppError>> = vec![Vec::new(); tickers.len()];
+        let mut pending: Vec<usize> = (0..tickers.len()).collect();
+
+        for provider in self.providers.iter().filter(|p| p.capabilities().quotes) {
+            if pending.is_empty() {
+                break;
+            }
+            let asked: Vec<String> = pending.iter().map(|&i| tickers[i].clone()).collect();
+
+            match provider.fetch_quotes(&asked).await {
+                Ok(answers) => {
+                    let mut answers = answers.into_iter();
+                    let mut failed = Vec::new();
+                    for i in pending {
+                        match answers.next() {
+                            Some(Ok(quote)) => quotes[i] = Some(quote),
+                            Some(Err(e)) => {
+                                errors[i].push(e);
+                                failed.push(i);
+                            }
+                            None => {
+                                errors[i].push(AppError::NotFound(format!("No quote for {}", tickers[i])));
+                                failed.push(i);
+                            }
+                        }
+                    }
+                    if !failed.is_empty() && failed.len() < asked.len() {
+                        println!("[Rust] Provider {} had no quote for {} of {} tickers", provider.name(), failed.len(), asked.len());
+                    }
+                    pending = failed;
+                }
+                Err(e) => {
+                    println!("[Rust] Provider {} failed quotes for {} tickers: {}", provider.name(), asked.len(), e);
+                    for &i in &pending {
+                        errors[i].push(e.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(quotes
+            .into_iter()
+            .zip(errors)
+            .map(|(quote, errors)| quote.ok_or_else(|| primary_error(errors)))
+            .collect())
+    }
+
+    /// First provider with any hits wins; an empty answer falls through to
+    /// the next one (the local listing only knows a few symbols)
+    async fn search_symbols(&self, query: &str) -> Result<Vec<SymbolMatch>, AppError> {
+        let mut errors = Vec::new();
+        let mut answered = false;
+
+        for provider in self.providers.iter().filter(|p| p.capabilities().search) {
+            match provider.search_symbols(query).await {
+                Ok(matches) if !matches.is_empty() => return Ok(matches),
+                Ok(_) => answered = true,
+                Err(e) => {
+                    println!("[Rust] Provider {} failed search for {:?}: {}", provider.name(), query, e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        if answered {
+            Ok(Vec::new())
+        } else {
+            Err(primary_error(errors))
+        }
+    }
+
+    /// Asks every provider until one lists the exact symbol; hits that only
+    /// share a prefix (the local listing's `SHOP.TO` for `SHOP`) fall through
+    async fn lookup_symbol(&self, symbol: &Symbol) -> Result<Option<SymbolMatch>, AppError> {
+        let mut errors = Vec::new();
+        let mut answered = false;
+
+        for provider in self.providers.iter().filter(|p| p.capabilities().search) {
+            match provider.lookup_symbol(symbol).await {
+                Ok(Some(found)) => return Ok(Some(found)),
+                Ok(None) => answered = true,
+                Err(e) => {
+                    println!("[Rust] Provider {} failed lookup for {}: {}", provider.name(), symbol, e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        if answered {
+            Ok(None)
+        } else {
+            Err(primary_error(errors))
+        }
+    }
+}
@@ -0,0 +1,648 @@
+use super::{DataProvider, ProviderCapabilities};
+use crate::error::AppError;
+use crate::models::{
+    daily_bar_time, AssetType, HistoricalData, HistoryRequest, Interval, LocalDataConfig, QuoteSnapshot, SymbolMatch,
+};
+use crate::symbol::{Exchange, Symbol};
+use async_trait::async_trait;
+use parquet::basic::{LogicalType, TimeUnit};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+// ==========================================
+// Local OHLCV Files (CSV / Parquet)
+// ==========================================
+
+/// Reads per-ticker history files (`AAPL.csv`, `005930.KS.parquet`, ...)
+/// from a user-selected folder.
+pub struct LocalFileProvider {
+    config: RwLock<LocalDataConfig>,
+}
+
+impl LocalFileProvider {
+    pub fn new(config: LocalDataConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+        }
+    }
+
+    /// Initial configuration from `STOCK_DATA_DIR` / `STOCK_DATA_OFFLINE`
+    pub fn from_env() -> Self {
+        let dir = std::env::var("STOCK_DATA_DIR").ok().filter(|d| !d.trim().is_empty());
+        let offline = std::env::var("STOCK_DATA_OFFLINE")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        Self::new(LocalDataConfig { dir, offline })
+    }
+
+    pub fn config(&self) -> LocalDataConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: LocalDataConfig) {
+        *self.config.write().unwrap() = config;
+    }
+
+    /// Offline mode: only local files are used, no network fallback
+    pub fn is_offline(&self) -> bool {
+        let config = self.config.read().unwrap();
+        config.offline && config.dir.is_some()
+    }
+
+    fn find_file(dir: &Path, ticker: &str) -> Option<PathBuf> {
+        let mut names = vec![ticker.to_string(), ticker.to_uppercase()];
+        // Canonical (BRK.B) and Yahoo (BRK-B) spellings
+        if let Ok(symbol) = Symbol::parse(ticker) {
+            names.push(symbol.to_string());
+            names.push(symbol.yahoo());
+        }
+
+        for name in names.iter() {
+            for ext in ["csv", "parquet"] {
+                let path = dir.join(format!("{}.{}", name, ext));
+                if path.is_file() {
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+}
+
+// ==========================================
+// Column Detection & Row Parsing
+// ==========================================
+
+struct ColumnMap {
+    date: usize,
+    open: usize,
+    high: usize,
+    low: usize,
+    close: usize,
+    adj_close: Option<usize>,
+    volume: Option<usize>,
+}
+
+/// Lowercase and strip separators so "Adj Close", "adj_close" and "AdjClose" compare equal
+fn normalize_header(name: &str) -> String {
+    name.trim()
+        .trim_start_matches('\u{feff}')
+        .to_lowercase()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-' | '.' | '"'))
+        .collect()
+}
+
+fn detect_columns(header: &[String], label: &str) -> Result<ColumnMap, AppError> {
+    let normalized: Vec<String> = header.iter().map(|h| normalize_header(h)).collect();
+    let find = |aliases: &[&str]| normalized.iter().position(|h| aliases.contains(&h.as_str()));
+    let require = |aliases: &[&str], name: &str| {
+        find(aliases).ok_or_else(|| AppError::Parse(format!(
+            "{}: no {} column found (header: {})",
+            label,
+            name,
+            header.join(", ")
+        )))
+    };
+
+    Ok(ColumnMap {
+        date: require(&["date", "datetime", "timestamp", "time", "일자", "날짜"], "date")?,
+        open: require(&["open", "시가"], "open")?,
+        high: require(&["high", "고가"], "high")?,
+        low: require(&["low", "저가"], "low")?,
+        close: require(&["close", "종가", "last", "price"], "close")?,
+        adj_close: find(&["adjclose", "adjustedclose", "수정종가"]),
+        volume: find(&["volume", "vol", "거래량"]),
+    })
+}
+
+/// Accepts ISO dates, `YYYY/MM/DD`, `YYYY.MM.DD`, `YYYYMMDD`, `MM/DD/YYYY`,
+/// and datetimes (time part is dropped).
+fn normalize_date(raw: &str) -> Option<chrono::NaiveDate> {
+    let trimmed = raw.trim().trim_matches('"');
+    let date_part = trimmed.split(['T', ' ']).next().unwrap_or(trimmed);
+
+    ["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d", "%Y%m%d", "%m/%d/%Y"]
+        .iter()
+        .find_map(|fmt| chrono::NaiveDate::parse_from_str(date_part, fmt).ok())
+}
+
+/// `row` is the file label and 1-based row, e.g. `AAPL.csv line 12`
+fn parse_number(raw: &str, name: &str, row: &str) -> Result<f64, AppError> {
+    let cleaned: String = raw.trim().chars().filter(|c| *c != ',' && *c != '"').collect();
+    if cleaned.is_empty() {
+        return Err(AppError::Parse(format!("{}: empty {}", row, name)));
+    }
+    let invalid = || AppError::Parse(format!("{}: invalid {} '{}'", row, name, raw.trim()));
+    let value = cleaned.parse::<f64>().map_err(|_| invalid())?;
+    if !value.is_finite() {
+        return Err(invalid());
+    }
+    Ok(value)
+}
+
+/// Build `HistoricalData` from a header and labelled rows.
+///
+/// Rows may be in either date order (broker exports are often newest-first);
+/// the result is always oldest-first. Any unparseable value fails the whole
+/// file with `Parse` naming the offending row; values that parse but make no
+/// sense (negative volume, unordered dates) are `InvalidData`.
+fn parse_table(
+    header: &[String],
+    rows: Vec<(String, Vec<String>)>,
+    label: &str,
+) -> Result<HistoricalData, AppError> {
+    let cols = detect_columns(header, label)?;
+
+    let mut bars = Vec::with_capacity(rows.len());
+    for (row, fields) in rows {
+        let get = |idx: usize| fields.get(idx).map(|s| s.as_str()).unwrap_or("");
+
+        let date = normalize_date(get(cols.date))
+            .ok_or_else(|| AppError::Parse(format!("{}: invalid date '{}'", row, get(cols.date).trim())))?;
+        let open = parse_number(get(cols.open), "open", &row)?;
+        let high = parse_number(get(cols.high), "high", &row)?;
+        let low = parse_number(get(cols.low), "low", &row)?;
+        let close = parse_number(get(cols.close), "close", &row)?;
+        let adj_close = match cols.adj_close {
+            Some(idx) => parse_number(get(idx), "adj close", &row)?,
+            None => close,
+        };
+        let volume = match cols.volume {
+            Some(idx) => {
+                let v = parse_number(get(idx), "volume", &row)?;
+                if v < 0.0 {
+                    return Err(AppError::InvalidData(format!("{}: negative volume", row)));
+                }
+                v as u64
+            }
+            None => 0,
+        };
+
+        bars.push((row, date, open, high, low, close, adj_close, volume));
+    }
+
+    if bars.is_empty() {
+        return Err(AppError::InvalidData(format!("{}: no rows", label)));
+    }
+
+    if bars.len() > 1 && bars[0].1 > bars[bars.len() - 1].1 {
+        bars.reverse();
+    }
+
+    for pair in bars.windows(2) {
+        if pair[1].1 <= pair[0].1 {
+            return Err(AppError::InvalidData(format!(
+                "{}: date {} is out of order or duplicated",
+                pair[1].0, pair[1].1
+            )));
+        }
+    }
+
+    let mut data = HistoricalData {
+        dates: Vec::with_capacity(bars.len()),
+        opens: Vec::with_capacity(bars.len()),
+        highs: Vec::with_capacity(bars.len()),
+        lows: Vec::with_capacity(bars.len()),
+        closes: Vec::with_capacity(bars.len()),
+        adj_closes: Vec::with_capacity(bars.len()),
+        volumes: Vec::with_capacity(bars.len()),
+        ..Default::default()
+    };
+
+    for (_, date, open, high, low, close, adj_close, volume) in bars {
+        data.dates.push(daily_bar_time(date));
+        data.opens.push(open);
+        data.highs.push(high);
+        data.lows.push(low);
+        data.closes.push(close);
+        data.adj_closes.push(adj_close);
+        data.volumes.push(volume);
+    }
+
+    Ok(data)
+}
+
+// ==========================================
+// CSV
+// ==========================================
+
+/// Split one CSV line, honoring double-quoted fields ("1,234.5")
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+/// Comma, semicolon or tab separated, picked from the header line.
+/// Rows are labelled with their 1-based line in the file.
+pub fn parse_history_csv(body: &str, label: &str) -> Result<HistoricalData, AppError> {
+    let mut lines = body
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+
+    let (_, header_line) = lines.next()
+        .ok_or_else(|| AppError::InvalidData(format!("{}: empty file", label)))?;
+    let delimiter = if header_line.contains(',') {
+        ','
+    } else if header_line.contains(';') {
+        ';'
+    } else {
+        '\t'
+    };
+    let header = split_csv_line(header_line, delimiter);
+
+    let rows = lines
+        .map(|(idx, line)| (format!("{} line {}", label, idx + 1), split_csv_line(line, delimiter)))
+        .collect();
+
+    parse_table(&header, rows, label)
+}
+
+// ==========================================
+// Parquet
+// ==========================================
+
+/// Calendar day (UTC) of an INT64 timestamp in `unit`
+fn timestamp_to_date(value: i64, unit: &TimeUnit) -> String {
+    let per_second: i64 = match unit {
+        TimeUnit::MILLIS(_) => 1_000,
+        TimeUnit::MICROS(_) => 1_000_000,
+        TimeUnit::NANOS(_) => 1_000_000_000,
+    };
+    let nanos_per_unit = 1_000_000_000 / per_second;
+    let nanos = value.rem_euclid(per_second) * nanos_per_unit;
+    chrono::DateTime::from_timestamp(value.div_euclid(per_second), nanos as u32)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// `unit` is the column's timestamp unit, if its logical type is a timestamp.
+/// The row reader only decodes millisecond and microsecond columns with a
+/// legacy converted type; nanoseconds (the pandas/pyarrow default) arrive as
+/// a plain `Long`.
+fn parquet_field_to_string(field: &parquet::record::Field, unit: Option<&TimeUnit>) -> String {
+    use parquet::record::Field;
+    match field {
+        Field::Null => String::new(),
+        Field::Str(s) => s.clone(),
+        Field::Date(days) => chrono::NaiveDate::from_num_days_from_ce_opt(*days + 719_163)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        Field::TimestampMillis(ms) => chrono::DateTime::from_timestamp_millis(*ms)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        Field::TimestampMicros(us) => chrono::DateTime::from_timestamp_micros(*us)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        Field::Float(v) => v.to_string(),
+        Field::Double(v) => v.to_string(),
+        Field::Int(v) => v.to_string(),
+        Field::Long(v) => match unit {
+            Some(unit) => timestamp_to_date(*v, unit),
+            None => v.to_string(),
+        },
+        Field::UInt(v) => v.to_string(),
+        Field::ULong(v) => v.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Rows are labelled with their 1-based position in the file
+pub fn read_history_parquet(path: &Path, label: &str) -> Result<HistoricalData, AppError> {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    let file_error = |e: &dyn std::fmt::Display| AppError::Parse(format!("{}: {}", label, e));
+    let file = std::fs::File::open(path).map_err(|e| file_error(&e))?;
+    let reader = SerializedFileReader::new(file).map_err(|e| file_error(&e))?;
+
+    let columns = reader.metadata().file_metadata().schema_descr().columns().to_vec();
+    let header: Vec<String> = columns.iter().map(|c| c.name().to_string()).collect();
+    let units: Vec<Option<TimeUnit>> = columns
+        .iter()
+        .map(|c| match c.logical_type() {
+            Some(LogicalType::Timestamp { unit, .. }) => Some(unit),
+            _ => None,
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    let iter = reader.get_row_iter(None).map_err(|e| file_error(&e))?;
+    for (idx, row) in iter.enumerate() {
+        let row_label = format!("{} row {}", label, idx + 1);
+        let row = row.map_err(|e| AppError::Parse(format!("{}: {}", row_label, e)))?;
+
+        let mut fields = vec![String::new(); header.len()];
+        for (name, field) in row.get_column_iter() {
+            if let Some(pos) = header.iter().position(|h| h == name) {
+                fields[pos] = parquet_field_to_string(field, units[pos].as_ref());
+            }
+        }
+        rows.push((row_label, fields));
+    }
+
+    parse_table(&header, rows, label)
+}
+
+// ==========================================
+// Symbol Search
+// ==========================================
+
+/// Optional listing in the data folder with `symbol,name,exchange,type,currency`
+/// columns (only `symbol` is required)
+pub const LISTING_FILE: &str = "symbols.csv";
+
+const MAX_SEARCH_RESULTS: usize = 10;
+
+/// Best guess when the listing does not say
+fn asset_type_of(symbol: &Symbol) -> AssetType {
+    match symbol.exchange {
+        Exchange::Index => AssetType::Index,
+        Exchange::Fx => AssetType::Currency,
+        Exchange::Futures => AssetType::Future,
+        _ => AssetType::Equity,
+    }
+}
+
+fn listing_entry(symbol: &Symbol, source: &str) -> SymbolMatch {
+    SymbolMatch {
+        symbol: symbol.to_string(),
+        name: None,
+        exchange: None,
+        asset_type: asset_type_of(symbol),
+        currency: symbol.currency(),
+        source: source.to_string(),
+    }
+}
+
+/// Rows of the listing file; unparseable symbols are skipped
+fn parse_listing(body: &str, source: &str) -> Vec<SymbolMatch> {
+    let mut lines = body.lines().filter(|l| !l.trim().is_empty());
+    let Some(header_line) = lines.next() else {
+        return Vec::new();
+    };
+    let delimiter = if header_line.contains(';') && !header_line.contains(',') { ';' } else { ',' };
+    let header: Vec<String> = split_csv_line(header_line, delimiter)
+        .iter()
+        .map(|h| normalize_header(h))
+        .collect();
+    let find = |aliases: &[&str]| header.iter().position(|h| aliases.contains(&h.as_str()));
+    let Some(symbol_col) = find(&["symbol", "ticker", "종목코드"]) else {
+        return Vec::new();
+    };
+    let name_col = find(&["name", "company", "종목명"]);
+    let exchange_col = find(&["exchange", "market", "시장"]);
+    let type_col = find(&["type", "assettype", "quotetype"]);
+    let currency_col = find(&["currency"]);
+
+    lines
+        .filter_map(|line| {
+            let fields = split_csv_line(line, delimiter);
+            let get = |col: Option<usize>| {
+                col.and_then(|idx| fields.get(idx))
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            };
+            let symbol = Symbol::parse(&get(Some(symbol_col))?).ok()?;
+            let mut entry = listing_entry(&symbol, source);
+            entry.name = get(name_col);
+            entry.exchange = get(exchange_col);
+            if let Some(asset_type) = get(type_col) {
+                entry.asset_type = AssetType::from_yahoo(&asset_type.to_uppercase());
+            }
+            if let Some(currency) = get(currency_col) {
+                entry.currency = Some(currency);
+            }
+            Some(entry)
+        })
+        .collect()
+}
+
+/// Listing file entries, then one bare entry per history file not listed there
+fn local_listings(dir: &Path, source: &str) -> Vec<SymbolMatch> {
+    let mut listings = std::fs::read_to_string(dir.join(LISTING_FILE))
+        .map(|body| parse_listing(&body, source))
+        .unwrap_or_default();
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return listings;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        let is_history = path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("csv") || e.eq_ignore_ascii_case("parquet"));
+        if !is_history || path.file_name().is_some_and(|n| n == LISTING_FILE) {
+            continue;
+        }
+        let Some(symbol) = path.file_stem().and_then(|s| Symbol::parse(&s.to_string_lossy()).ok()) else {
+            continue;
+        };
+        if !listings.iter().any(|l| l.symbol == symbol.to_string()) {
+            listings.push(listing_entry(&symbol, source));
+        }
+    }
+    listings
+}
+
+/// Exact ticker first, then ticker prefix, then name substring
+pub fn search_listings(listings: Vec<SymbolMatch>, query: &str) -> Vec<SymbolMatch> {
+    let upper = query.trim().to_uppercase();
+    let lower = query.trim().to_lowercase();
+    let canonical = Symbol::parse(query).map(|s| s.to_string()).unwrap_or_else(|_| upper.clone());
+
+    let mut ranked: Vec<(u8, SymbolMatch)> = listings
+        .into_iter()
+        .filter_map(|entry| {
+            let rank = if entry.symbol == canonical || entry.symbol == upper {
+                0
+            } else if entry.symbol.starts_with(&upper) {
+                1
+            } else if entry.name.as_ref().is_some_and(|n| n.to_lowercase().contains(&lower)) {
+                2
+            } else {
+                return None;
+            };
+            Some((rank, entry))
+        })
+        .collect();
+    ranked.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.symbol.cmp(&b.1.symbol)));
+    ranked.into_iter().take(MAX_SEARCH_RESULTS).map(|(_, entry)| entry).collect()
+}
+
+#[async_trait]
+impl DataProvider for LocalFileProvider {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            history: self.config.read().unwrap().dir.is_some(),
+            quotes: false,
+            adjusted_close: true,
+            search: self.config.read().unwrap().dir.is_some(),
+        }
+    }
+
+    async fn fetch_history(&self, ticker: &str, request: &HistoryRequest) -> Result<HistoricalData, AppError> {
+        if request.interval != Interval::OneDay {
+            return Err(AppError::Unsupported("Local files only provide daily bars".to_string()));
+        }
+
+        let dir = self.config().dir
+            .ok_or_else(|| AppError::Unsupported("Local data folder not configured".to_string()))?;
+        let path = Self::find_file(Path::new(&dir), ticker)
+            .ok_or_else(|| AppError::NotFound(format!("No local file for {} in {}", ticker, dir)))?;
+
+        let label = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| ticker.to_string());
+
+        println!("[Rust] Loading local history: {}", path.display());
+
+        let is_parquet = path
+            .extension()
+            .map(|e| e.eq_ignore_ascii_case("parquet"))
+            .unwrap_or(false);
+
+        let mut data = tokio::task::spawn_blocking(move || {
+            if is_parquet {
+                read_history_parquet(&path, &label)
+            } else {
+                let body = std::fs::read_to_string(&path)
+                    .map_err(|e| AppError::Parse(format!("{}: {}", label, e)))?;
+                parse_history_csv(&body, &label)
+            }
+        })
+        .await??;
+
+        data.source = self.name().to_string();
+        Ok(data)
+    }
+
+    async fn fetch_quote(&self, _ticker: &str) -> Result<QuoteSnapshot, AppError> {
+        Err(AppError::Unsupported("Quotes not supported by local files".to_string()))
+    }
+
+    async fn search_symbols(&self, query: &str) -> Result<Vec<SymbolMatch>, AppError> {
+        let dir = self.config().dir
+            .ok_or_else(|| AppError::Unsupported("Local data folder not configured".to_string()))?;
+        let source = self.name().to_string();
+        let query = query.to_string();
+
+        let matches = tokio::task::spawn_blocking(move || {
+            search_listings(local_listings(Path::new(&dir), &source), &query)
+        })
+        .await?;
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KOREAN_BOM: &str = include_str!("testdata/local_korean_bom.csv");
+    const SEMICOLON: &str = include_str!("testdata/local_semicolon.csv");
+    const TAB: &str = include_str!("testdata/local_tab.csv");
+    const BAD_CLOSE: &str = include_str!("testdata/local_bad_close.csv");
+    const BAD_DATE: &str = include_str!("testdata/local_bad_date.csv");
+
+    fn testdata(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("src/providers/testdata").join(name)
+    }
+
+    fn day(date: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    // Naive (not UTC-adjusted) INT64 timestamps, as pandas/pyarrow write them
+    #[test]
+    fn parquet_timestamps_in_every_unit_become_dates() {
+        for unit in ["millis", "micros", "nanos"] {
+            let name = format!("local_timestamp_{}.parquet", unit);
+            let data = read_history_parquet(&testdata(&name), &name).unwrap_or_else(|e| panic!("{}: {}", name, e));
+
+            let dates: Vec<_> = data.dates.iter().map(|d| d.date_naive()).collect();
+            assert_eq!(dates, vec![day("2024-01-02"), day("2024-01-03"), day("2024-01-04")], "{}", name);
+            assert_eq!(data.closes[2], 181.91, "{}", name);
+            assert_eq!(data.volumes[0], 82_488_674, "{}", name);
+        }
+    }
+
+    #[test]
+    fn timestamps_before_the_epoch_round_down() {
+        let unit = TimeUnit::NANOS(Default::default());
+        assert_eq!(timestamp_to_date(-1, &unit), "1969-12-31");
+        assert_eq!(timestamp_to_date(1_704_153_600_000_000_000, &unit), "2024-01-02");
+    }
+
+    #[test]
+    fn korean_headers_behind_a_bom_newest_first() {
+        let data = parse_history_csv(KOREAN_BOM, "005930.KS.csv").unwrap();
+
+        // Broker exports list the latest day first; history is oldest-first
+        let dates: Vec<_> = data.dates.iter().map(|d| d.date_naive()).collect();
+        assert_eq!(dates, vec![day("2024-01-02"), day("2024-01-03"), day("2024-01-04")]);
+        assert_eq!(data.opens[0], 78_200.0);
+        assert_eq!(data.closes, vec![79_600.0, 74_700.0, 77_000.0]);
+        assert_eq!(data.adj_closes, data.closes);
+        assert_eq!(data.volumes[2], 15_324_439);
+    }
+
+    #[test]
+    fn semicolon_file_with_aliased_columns() {
+        let data = parse_history_csv(SEMICOLON, "AAPL.csv").unwrap();
+
+        assert_eq!(data.dates[1].date_naive(), day("2024-01-03"));
+        assert_eq!(data.lows[0], 183.885);
+        // "Price" is the close, "Adj_Close" the adjusted close, "Vol" the volume
+        assert_eq!(data.closes, vec![185.64, 184.25]);
+        assert_eq!(data.adj_closes, vec![184.94, 183.56]);
+        assert_eq!(data.volumes, vec![82_488_674, 58_414_460]);
+    }
+
+    #[test]
+    fn tab_separated_file() {
+        let data = parse_history_csv(TAB, "AAPL.csv").unwrap();
+
+        assert_eq!(data.dates[0].date_naive(), day("2024-01-02"));
+        assert_eq!(data.highs, vec![188.44, 185.88]);
+        assert_eq!(data.closes[1], 184.25);
+    }
+
+    #[test]
+    fn non_numeric_close_names_the_file_line() {
+        let error = parse_history_csv(BAD_CLOSE, "AAPL.csv").unwrap_err();
+
+        // Line 5 of the file: the blank line still counts
+        assert_eq!(error, AppError::Parse("AAPL.csv line 5: invalid close 'n/a'".to_string()));
+    }
+
+    #[test]
+    fn bad_date_names_the_file_line() {
+        let error = parse_history_csv(BAD_DATE, "AAPL.csv").unwrap_err();
+
+        assert_eq!(error, AppError::Parse("AAPL.csv line 3: invalid date '2024-02-30'".to_string()));
+    }
+
+    #[test]
+    fn missing_column_lists_the_header() {
+        let error = parse_history_csv("Date,Open,High,Close\n2024-01-02,1,2,1.5\n", "AAPL.csv").unwrap_err();
+
+        assert_eq!(error.code(), crate::error::ErrorCode::ParseError);
+        assert!(error.to_string().contains("no low column found (header: Date, Open, High, Close)"), "{}", error);
+    }
+}
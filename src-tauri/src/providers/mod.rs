@@ -0,0 +1,68 @@
+pub mod chain;
+pub mod local;
+pub mod stooq;
+pub mod yahoo;
+
+use crate::error::AppError;
+use crate::models::{HistoricalData, HistoryRequest, QuoteSnapshot, SymbolMatch};
+use crate::symbol::Symbol;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+pub use chain::ProviderChain;
+pub use local::LocalFileProvider;
+pub use stooq::StooqProvider;
+pub use yahoo::YahooProvider;
+
+// ==========================================
+// Market Data Provider Abstraction
+// ==========================================
+
+/// What a provider is able to serve.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCapabilities {
+    pub history: bool,
+    pub quotes: bool,
+    pub adjusted_close: bool,
+    /// Can look up symbols by ticker or company name
+    pub search: bool,
+}
+
+/// A source of historical bars and quotes (Yahoo, CSV files, ...).
+///
+/// Implementations receive the ticker as the user typed it and are
+/// responsible for converting it to their own symbol format.
+#[async_trait]
+pub trait DataProvider: Send + Sync {
+    /// Short identifier used in logs and results (e.g. "yahoo").
+    fn name(&self) -> &str;
+
+    fn capabilities(&self) -> ProviderCapabilities;
+
+    /// Providers may return more bars than requested; callers merge by date.
+    async fn fetch_history(&self, ticker: &str, request: &HistoryRequest) -> Result<HistoricalData, AppError>;
+
+    async fn fetch_quote(&self, ticker: &str) -> Result<QuoteSnapshot, AppError>;
+
+    /// Quotes for several tickers, in order. The outer error means none could
+    /// be asked for; providers with a multi-symbol endpoint answer in one request.
+    async fn fetch_quotes(&self, tickers: &[String]) -> Result<Vec<Result<QuoteSnapshot, AppError>>, AppError> {
+        let mut quotes = Vec::with_capacity(tickers.len());
+        for ticker in tickers {
+            quotes.push(self.fetch_quote(ticker).await);
+        }
+        Ok(quotes)
+    }
+
+    /// Listings matching `query` (ticker or company name), best match first.
+    async fn search_symbols(&self, query: &str) -> Result<Vec<SymbolMatch>, AppError>;
+
+    /// The listing for exactly `symbol`, if this provider knows it.
+    /// Near misses (`SHOP.TO` for `SHOP`) are `None`.
+    async fn lookup_symbol(&self, symbol: &Symbol) -> Result<Option<SymbolMatch>, AppError> {
+        let canonical = symbol.to_string();
+        let matches = self.search_symbols(&symbol.yahoo()).await?;
+        Ok(matches.into_iter().find(|m| m.symbol == canonical))
+    }
+}
@@ -0,0 +1,293 @@
+use super::{DataProvider, ProviderCapabilities};
+use crate::breaker::CircuitBreaker;
+use crate::commands::stock::get_random_user_agent;
+use crate::error::AppError;
+use crate::models::{daily_bar_time, HistoricalData, HistoryRequest, Interval, QuoteSnapshot, SymbolMatch};
+use crate::ratelimit::{backoff_delay, retry_after_secs, RateLimiter};
+use crate::symbol::Symbol;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+// ==========================================
+// Stooq (daily OHLCV CSV)
+// ==========================================
+
+pub const STOOQ_HOST: &str = "stooq.com";
+
+pub struct StooqProvider {
+    client: reqwest::Client,
+    limiter: Arc<RateLimiter>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl StooqProvider {
+    pub fn new(client: reqwest::Client, limiter: Arc<RateLimiter>, breaker: Arc<CircuitBreaker>) -> Self {
+        Self { client, limiter, breaker }
+    }
+
+    /// One CSV download, skipped while the host's circuit is open
+    async fn fetch_csv(&self, url: &str) -> Result<String, AppError> {
+        self.breaker.allow(STOOQ_HOST)?;
+        self.limiter.acquire(STOOQ_HOST).await;
+
+        let result = self.request_csv(url).await;
+        match &result {
+            Err(e) if e.is_transient() => self.breaker.record_failure(STOOQ_HOST),
+            _ => self.breaker.record_success(STOOQ_HOST),
+        }
+        result
+    }
+
+    async fn request_csv(&self, url: &str) -> Result<String, AppError> {
+        let response = self.client
+            .get(url)
+            .header("User-Agent", get_random_user_agent())
+            .send()
+            .await
+            .map_err(|e| AppError::Network(e.to_string()))?;
+
+        if response.status() == 429 {
+            // No retry here; the chain already has another source to try
+            let retry_after = retry_after_secs(response.headers());
+            let pause = retry_after.map_or_else(|| backoff_delay(1), Duration::from_secs);
+            self.limiter.pause(STOOQ_HOST, pause);
+            return Err(AppError::RateLimited { retry_after });
+        }
+        if !response.status().is_success() {
+            return Err(AppError::Http { status: response.status().as_u16() });
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AppError::Parse(e.to_string()))?;
+
+        // Checked here rather than only in the parser so the breaker counts it
+        if is_daily_limit_notice(&body) {
+            println!("[Rust] Stooq daily hits limit exceeded");
+            return Err(daily_limit_error());
+        }
+        Ok(body)
+    }
+}
+
+/// Over its daily quota Stooq answers 200 with a plain-text notice, not CSV
+fn is_daily_limit_notice(body: &str) -> bool {
+    body.trim_start().to_lowercase().starts_with("exceeded the daily hits limit")
+}
+
+/// The quota resets at midnight, far past any useful retry
+fn daily_limit_error() -> AppError {
+    AppError::RateLimited { retry_after: None }
+}
+
+/// Parse a Stooq daily CSV (`Date,Open,High,Low,Close,Volume`) for `symbol`.
+///
+/// Stooq prices are already split-adjusted, so `adj_closes` mirrors `closes`.
+/// Indices and some FX series omit the volume column. Unknown symbols come
+/// back as 200 with a "No data" body, which is `NotFound`; the daily quota
+/// notice is `RateLimited`. Row numbers in errors are 1-based file lines.
+pub fn parse_stooq_csv(body: &str, symbol: &str) -> Result<HistoricalData, AppError> {
+    let no_data = || AppError::NotFound(format!("No Stooq data for {}", symbol));
+    let parse_error = |message: String| AppError::Parse(format!("Stooq {}: {}", symbol, message));
+
+    if is_daily_limit_notice(body) {
+        return Err(daily_limit_error());
+    }
+
+    // Numbered before blank lines are dropped so rows match the file
+    let mut lines = body
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines.next().ok_or_else(no_data)?;
+    if header.trim().eq_ignore_ascii_case("No data") {
+        return Err(no_data());
+    }
+
+    let columns: Vec<String> = header.split(',').map(|c| c.trim().to_lowercase()).collect();
+    let col = |name: &str| {
+        columns
+            .iter()
+            .position(|c| c == name)
+            .ok_or_else(|| parse_error(format!("CSV missing {} column", name)))
+    };
+    let date_idx = col("date")?;
+    let open_idx = col("open")?;
+    let high_idx = col("high")?;
+    let low_idx = col("low")?;
+    let close_idx = col("close")?;
+    let volume_idx = col("volume").ok();
+
+    let mut data = HistoricalData::default();
+
+    for (row, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+
+        let field = |idx: usize, name: &str| -> Result<f64, AppError> {
+            fields
+                .get(idx)
+                .ok_or_else(|| parse_error(format!("row {}: missing {}", row, name)))?
+                .parse::<f64>()
+                .map_err(|e| parse_error(format!("row {}: invalid {} ({})", row, name, e)))
+        };
+
+        let raw_date = fields
+            .get(date_idx)
+            .ok_or_else(|| parse_error(format!("row {}: missing date", row)))?;
+        let date = chrono::NaiveDate::parse_from_str(raw_date, "%Y-%m-%d")
+            .map_err(|_| parse_error(format!("row {}: invalid date '{}'", row, raw_date)))?;
+        let close = field(close_idx, "close")?;
+
+        data.dates.push(daily_bar_time(date));
+        data.opens.push(field(open_idx, "open")?);
+        data.highs.push(field(high_idx, "high")?);
+        data.lows.push(field(low_idx, "low")?);
+        data.closes.push(close);
+        data.adj_closes.push(close);
+        data.volumes.push(match volume_idx {
+            Some(idx) => field(idx, "volume")? as u64,
+            None => 0,
+        });
+    }
+
+    if data.dates.is_empty() {
+        return Err(no_data());
+    }
+
+    Ok(data)
+}
+
+#[async_trait]
+impl DataProvider for StooqProvider {
+    fn name(&self) -> &str {
+        "stooq"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            history: true,
+            quotes: false,
+            adjusted_close: false,
+            search: false,
+        }
+    }
+
+    async fn fetch_history(&self, ticker: &str, request: &HistoryRequest) -> Result<HistoricalData, AppError> {
+        let symbol = Symbol::parse(ticker)?.stooq()?;
+        let stooq_interval = match request.interval {
+            Interval::OneDay => "d",
+            Interval::OneWeek => "w",
+            Interval::OneMonth => "m",
+            other => {
+                return Err(AppError::Unsupported(format!(
+                    "Interval {} not supported by Stooq",
+                    other.as_str()
+                )))
+            }
+        };
+
+        let today = chrono::Utc::now().date_naive();
+        let end = request.end.unwrap_or(today);
+        let mut url = format!(
+            "https://{}/q/d/l/?s={}&i={}&d2={}",
+            STOOQ_HOST,
+            symbol,
+            stooq_interval,
+            end.format("%Y%m%d")
+        );
+        if let Some(start) = request.window_start(today) {
+            url.push_str(&format!("&d1={}", start.format("%Y%m%d")));
+        }
+
+        println!("[Rust] Fetching Stooq CSV for: {}", symbol);
+        let body = self.fetch_csv(&url).await?;
+
+        let mut data = parse_stooq_csv(&body, &symbol)?;
+        data.source = self.name().to_string();
+        Ok(data)
+    }
+
+    async fn fetch_quote(&self, _ticker: &str) -> Result<QuoteSnapshot, AppError> {
+        Err(AppError::Unsupported("Quotes not supported by Stooq".to_string()))
+    }
+
+    async fn search_symbols(&self, _query: &str) -> Result<Vec<SymbolMatch>, AppError> {
+        Err(AppError::Unsupported("Symbol search not supported by Stooq".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    // Recorded stooq.com/q/d/l/ responses (CRLF line endings as served)
+    const AAPL_US: &str = include_str!("testdata/stooq_aapl_us.csv");
+    const NO_DATA: &str = include_str!("testdata/stooq_no_data.csv");
+    const USDKRW: &str = include_str!("testdata/stooq_usdkrw.csv");
+    const MALFORMED: &str = include_str!("testdata/stooq_malformed.csv");
+    const BLANK_LINES: &str = include_str!("testdata/stooq_blank_lines.csv");
+    const DAILY_LIMIT: &str = include_str!("testdata/stooq_daily_limit.txt");
+
+    #[test]
+    fn parses_daily_bars() {
+        let data = parse_stooq_csv(AAPL_US, "aapl.us").unwrap();
+
+        assert_eq!(data.dates.len(), 4);
+        assert_eq!(data.dates[0].date_naive(), chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap());
+        assert_eq!(data.opens[0], 187.15);
+        assert_eq!(data.highs[2], 183.0872);
+        assert_eq!(data.closes[3], 181.18);
+        assert_eq!(data.adj_closes, data.closes);
+        assert_eq!(data.volumes[0], 82_488_674);
+    }
+
+    #[test]
+    fn no_data_body_is_not_found() {
+        let error = parse_stooq_csv(NO_DATA, "zzzz.us").unwrap_err();
+        assert_eq!(error.code(), ErrorCode::NotFound);
+        assert!(error.to_string().contains("zzzz.us"));
+
+        let empty = parse_stooq_csv("", "zzzz.us").unwrap_err();
+        assert_eq!(empty.code(), ErrorCode::NotFound);
+    }
+
+    #[test]
+    fn missing_volume_column_means_zero_volume() {
+        let data = parse_stooq_csv(USDKRW, "usdkrw").unwrap();
+
+        assert_eq!(data.dates.len(), 2);
+        assert_eq!(data.closes[1], 1290.41);
+        assert_eq!(data.volumes, vec![0, 0]);
+    }
+
+    #[test]
+    fn malformed_row_is_a_parse_error_naming_the_row() {
+        let error = parse_stooq_csv(MALFORMED, "aapl.us").unwrap_err();
+
+        assert_eq!(error.code(), ErrorCode::ParseError);
+        assert!(error.to_string().contains("row 3: invalid high"), "{}", error);
+    }
+
+    #[test]
+    fn row_numbers_count_blank_lines() {
+        let error = parse_stooq_csv(BLANK_LINES, "aapl.us").unwrap_err();
+
+        assert_eq!(error.code(), ErrorCode::ParseError);
+        assert!(error.to_string().contains("row 6: invalid volume"), "{}", error);
+    }
+
+    #[test]
+    fn daily_hits_limit_is_rate_limited() {
+        let error = parse_stooq_csv(DAILY_LIMIT, "aapl.us").unwrap_err();
+
+        assert_eq!(error.code(), ErrorCode::RateLimited);
+        assert!(error.is_transient());
+        assert!(is_daily_limit_notice(DAILY_LIMIT));
+        assert!(!is_daily_limit_notice(AAPL_US));
+    }
+}
@@ -0,0 +1,636 @@
+use super::{DataProvider, ProviderCapabilities};
+use crate::breaker::CircuitBreaker;
+use crate::commands::stock::get_random_user_agent;
+use crate::error::AppError;
+use crate::models::{
+    AssetType, BarSession, Dividend, HistoricalData, HistoryRequest, Interval, InvalidTimestamp, MarketState,
+    QuoteSnapshot, Split, SymbolMatch, YahooEvents, YahooMeta, YahooPeriod, YahooQuote, YahooResponse, YahooResult,
+    YahooSearchResponse, YahooSparkResponse,
+};
+use std::collections::HashMap;
+use crate::ratelimit::{backoff_delay, retry_after_secs, RateLimiter, MAX_RETRY_WAIT_SECS};
+use crate::symbol::Symbol;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+// ==========================================
+// Yahoo Finance (v8 chart API)
+// ==========================================
+
+pub const YAHOO_QUERY1_HOST: &str = "query1.finance.yahoo.com";
+pub const YAHOO_QUERY2_HOST: &str = "query2.finance.yahoo.com";
+
+const MAX_ATTEMPTS: u32 = 3;
+const SEARCH_RESULTS: &str = "10";
+/// Yahoo rejects spark requests for more symbols than this
+const SPARK_MAX_SYMBOLS: usize = 20;
+
+pub struct YahooProvider {
+    client: reqwest::Client,
+    limiter: Arc<RateLimiter>,
+    breaker: Arc<CircuitBreaker>,
+    name: String,
+    host: String,
+}
+
+impl YahooProvider {
+    pub fn new(client: reqwest::Client, limiter: Arc<RateLimiter>, breaker: Arc<CircuitBreaker>) -> Self {
+        Self::with_host(client, limiter, breaker, "yahoo", YAHOO_QUERY1_HOST)
+    }
+
+    /// Same API on a different host (query2 is rate-limited separately)
+    pub fn with_host(
+        client: reqwest::Client,
+        limiter: Arc<RateLimiter>,
+        breaker: Arc<CircuitBreaker>,
+        name: &str,
+        host: &str,
+    ) -> Self {
+        Self {
+            client,
+            limiter,
+            breaker,
+            name: name.to_string(),
+            host: host.to_string(),
+        }
+    }
+
+    /// One chart call, skipped while the host's circuit is open
+    async fn fetch_chart(&self, ticker: &str, query: &str) -> Result<YahooResult, AppError> {
+        // Yahoo uses dashes for share classes (BRK.B -> BRK-B) and keeps exchange suffixes
+        let formatted_ticker = Symbol::parse(ticker)?.yahoo();
+        self.breaker.allow(&self.host)?;
+
+        let result = self.request_chart(&formatted_ticker, query).await;
+        match &result {
+            Err(e) if e.is_transient() => self.breaker.record_failure(&self.host),
+            _ => self.breaker.record_success(&self.host),
+        }
+        result
+    }
+
+    /// `formatted_ticker` is already in Yahoo's symbol format
+    async fn request_chart(&self, formatted_ticker: &str, query: &str) -> Result<YahooResult, AppError> {
+        let url = format!(
+            "https://{}/v8/finance/chart/{}?{}",
+            self.host, formatted_ticker, query
+        );
+
+        println!("[Rust] Fetching data for: {}", formatted_ticker);
+        use std::io::Write;
+        let _ = std::io::stdout().flush();
+
+        let mut last_error = AppError::Network("no attempt made".to_string());
+        let mut fetched_data: Option<YahooResponse> = None;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            if attempt > 1 {
+                // Other calls tripped the breaker meanwhile: stop hammering the host
+                if self.breaker.is_open(&self.host) {
+                    break;
+                }
+                let delay = backoff_delay(attempt - 1);
+                println!("[Rust] Retry attempt {} for {} in {:?}", attempt, formatted_ticker, delay);
+                tokio::time::sleep(delay).await;
+            }
+
+            self.limiter.acquire(&self.host).await;
+            let response_result = self.client
+                .get(&url)
+                .header("User-Agent", get_random_user_agent())
+                .send()
+                .await;
+
+            match response_result {
+                Ok(response) => {
+                    if response.status() == 429 {
+                        let retry_after = retry_after_secs(response.headers());
+                        println!("[Rust] Rate Limit (429) for {} (Retry-After: {:?})", formatted_ticker, retry_after);
+                        last_error = AppError::RateLimited { retry_after };
+                        match retry_after {
+                            // Waiting that long would stall the whole batch
+                            Some(secs) if secs > MAX_RETRY_WAIT_SECS => {
+                                self.limiter.pause(&self.host, Duration::from_secs(secs));
+                                break;
+                            }
+                            // Every caller of this host waits, not just this one
+                            Some(secs) => self.limiter.pause(&self.host, Duration::from_secs(secs)),
+                            None => self.limiter.pause(&self.host, backoff_delay(attempt)),
+                        }
+                        continue;
+                    }
+
+                    // Unknown or delisted symbol: the body says which, retrying won't help
+                    if response.status() == 404 {
+                        println!("[Rust] Not found (404) for {}", formatted_ticker);
+                        let description = response
+                            .json::<YahooResponse>()
+                            .await
+                            .ok()
+                            .and_then(|r| r.chart.error)
+                            .and_then(|e| e.get("description")?.as_str().map(str::to_string))
+                            .unwrap_or_else(|| format!("No data found for {}", formatted_ticker));
+                        return Err(not_found_error(description));
+                    }
+
+                    if !response.status().is_success() {
+                        println!("[Rust] HTTP Error {} for {}", response.status(), formatted_ticker);
+                        last_error = AppError::Http { status: response.status().as_u16() };
+                        continue;
+                    }
+
+                    println!("[Rust] Success fetch for {}", formatted_ticker);
+
+                    match response.json::<YahooResponse>().await {
+                        Ok(data) => {
+                            fetched_data = Some(data);
+                            break;
+                        },
+                        Err(e) => {
+                            last_error = AppError::Parse(e.to_string());
+                            continue;
+                        }
+                    }
+                }
+                Err(e) => {
+                    println!("[Rust] Network error for {}: {}", formatted_ticker, e);
+                    last_error = AppError::Network(e.to_string());
+                }
+            }
+        }
+
+        let data = fetched_data.ok_or(last_error)?;
+
+        let Some(results) = data.chart.result else {
+            let description = data.chart.error
+                .and_then(|e| e.get("description")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| format!("No data available for {}", formatted_ticker));
+            return Err(not_found_error(description));
+        };
+
+        results
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::NotFound(format!("Empty result for {}", formatted_ticker)))
+    }
+}
+
+impl YahooProvider {
+    /// One search call, skipped while the host's circuit is open
+    async fn search(&self, query: &str) -> Result<Vec<SymbolMatch>, AppError> {
+        self.breaker.allow(&self.host)?;
+
+        let result = self.request_search(query).await;
+        match &result {
+            Err(e) if e.is_transient() => self.breaker.record_failure(&self.host),
+            _ => self.breaker.record_success(&self.host),
+        }
+        result
+    }
+
+    /// Single attempt: searches run as the user types, a stale retry is useless
+    async fn request_search(&self, query: &str) -> Result<Vec<SymbolMatch>, AppError> {
+        let url = format!("https://{}/v1/finance/search", self.host);
+        println!("[Rust] Searching symbols for: {:?}", query);
+
+        self.limiter.acquire(&self.host).await;
+        let response = self.client
+            .get(&url)
+            .query(&[
+                ("q", query),
+                ("quotesCount", SEARCH_RESULTS),
+                ("newsCount", "0"),
+                ("listsCount", "0"),
+            ])
+            .header("User-Agent", get_random_user_agent())
+            .send()
+            .await?;
+
+        if response.status() == 429 {
+            let retry_after = retry_after_secs(response.headers());
+            println!("[Rust] Rate Limit (429) for search {:?} (Retry-After: {:?})", query, retry_after);
+            let pause = retry_after.map(Duration::from_secs).unwrap_or_else(|| backoff_delay(1));
+            self.limiter.pause(&self.host, pause);
+            return Err(AppError::RateLimited { retry_after });
+        }
+        if !response.status().is_success() {
+            println!("[Rust] HTTP Error {} for search {:?}", response.status(), query);
+            return Err(AppError::Http { status: response.status().as_u16() });
+        }
+
+        let data = response.json::<YahooSearchResponse>().await
+            .map_err(|e| AppError::Parse(e.to_string()))?;
+
+        Ok(data.quotes
+            .into_iter()
+            .filter_map(|quote| {
+                // Yahoo also lists symbols we cannot chart (options, odd venues)
+                let symbol = Symbol::parse(&quote.symbol).ok()?;
+                Some(SymbolMatch {
+                    symbol: symbol.to_string(),
+                    name: quote.longname.or(quote.shortname),
+                    exchange: quote.exch_disp.or(quote.exchange),
+                    asset_type: quote.quote_type.as_deref().map_or(AssetType::Other, AssetType::from_yahoo),
+                    currency: symbol.currency(),
+                    source: self.name.clone(),
+                })
+            })
+            .collect())
+    }
+
+    /// One spark call, skipped while the host's circuit is open
+    async fn spark(&self, symbols: &[String]) -> Result<YahooSparkResponse, AppError> {
+        self.breaker.allow(&self.host)?;
+
+        let result = self.request_spark(symbols).await;
+        match &result {
+            Err(e) if e.is_transient() => self.breaker.record_failure(&self.host),
+            _ => self.breaker.record_success(&self.host),
+        }
+        result
+    }
+
+    /// Single attempt: quotes are polled, the next poll is the retry.
+    /// `symbols` are already in Yahoo's format.
+    async fn request_spark(&self, symbols: &[String]) -> Result<YahooSparkResponse, AppError> {
+        let url = format!("https://{}/v7/finance/spark", self.host);
+        let joined = symbols.join(",");
+        println!("[Rust] Fetching quotes for: {}", joined);
+
+        self.limiter.acquire(&self.host).await;
+        let response = self.client
+            .get(&url)
+            .query(&[
+                ("symbols", joined.as_str()),
+                ("range", "1d"),
+                ("interval", "1d"),
+                ("indicators", "close"),
+            ])
+            .header("User-Agent", get_random_user_agent())
+            .send()
+            .await?;
+
+        if response.status() == 429 {
+            let retry_after = retry_after_secs(response.headers());
+            println!("[Rust] Rate Limit (429) for quotes (Retry-After: {:?})", retry_after);
+            let pause = retry_after.map(Duration::from_secs).unwrap_or_else(|| backoff_delay(1));
+            self.limiter.pause(&self.host, pause);
+            return Err(AppError::RateLimited { retry_after });
+        }
+        if !response.status().is_success() {
+            println!("[Rust] HTTP Error {} for quotes", response.status());
+            return Err(AppError::Http { status: response.status().as_u16() });
+        }
+
+        response.json::<YahooSparkResponse>().await
+            .map_err(|e| AppError::Parse(e.to_string()))
+    }
+}
+
+/// Yahoo reports delisted symbols as "No data found, symbol may be delisted"
+fn not_found_error(description: String) -> AppError {
+    if description.to_lowercase().contains("delisted") {
+        AppError::DelistedSymbol(description)
+    } else {
+        AppError::NotFound(description)
+    }
+}
+
+/// Convert a chart result into column-oriented history.
+///
+/// Bar times are converted to the exchange offset so daily bars land on the
+/// correct trading day for non-US exchanges. Intraday bars are also tagged
+/// with their trading session.
+pub fn parse_chart_result(result: YahooResult, interval: Interval) -> Result<HistoricalData, AppError> {
+    let timestamps = result.timestamp.unwrap_or_default();
+    let quote = result.indicators.quote.first()
+        .ok_or_else(|| AppError::Parse("No quote data".to_string()))?;
+
+    let provided_adj_closes = result.indicators.adjclose
+        .and_then(|ac| ac.into_iter().next())
+        .map(|ac| ac.adjclose);
+    let has_adj_closes = provided_adj_closes.is_some();
+    let adj_closes = provided_adj_closes.unwrap_or_else(|| quote.close.clone());
+
+    let gmtoffset = result.meta.gmtoffset.and_then(|o| i32::try_from(o).ok());
+    let offset = gmtoffset
+        .and_then(chrono::FixedOffset::east_opt)
+        .unwrap_or(chrono::FixedOffset::east_opt(0).unwrap());
+
+    let dates = timestamps.iter().enumerate().map(|(index, &ts)| {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.with_timezone(&offset))
+            .ok_or(InvalidTimestamp { index, timestamp: ts })
+    }).collect::<Result<Vec<_>, _>>()?;
+
+    // A bar is missing when any price field is null; null volume alone is
+    // common for indices and FX and just means zero.
+    let missing: Vec<bool> = (0..dates.len()).map(|i| {
+        let null = |v: &[Option<f64>]| v.get(i).copied().flatten().is_none();
+        null(&quote.open) || null(&quote.high) || null(&quote.low) || null(&quote.close) || null(&adj_closes)
+    }).collect();
+
+    let sessions = if interval.is_intraday() {
+        classify_sessions(&timestamps, &result.meta)
+    } else {
+        Vec::new()
+    };
+
+    let (dividends, splits) = parse_events(result.events.as_ref(), offset)?;
+
+    let mut data = HistoricalData {
+        dates,
+        opens: quote.open.iter().map(|v| v.unwrap_or(f64::NAN)).collect(),
+        highs: quote.high.iter().map(|v| v.unwrap_or(f64::NAN)).collect(),
+        lows: quote.low.iter().map(|v| v.unwrap_or(f64::NAN)).collect(),
+        closes: quote.close.iter().map(|v| v.unwrap_or(f64::NAN)).collect(),
+        adj_closes: adj_closes.iter().map(|v| v.unwrap_or(f64::NAN)).collect(),
+        volumes: quote.volume.iter().map(|v| v.unwrap_or(0)).collect(),
+        source: String::new(),
+        timezone: result.meta.exchange_timezone_name.clone(),
+        gmtoffset,
+        currency: result.meta.currency.clone(),
+        sessions,
+        missing: if missing.contains(&true) { missing } else { Vec::new() },
+        quality: None,
+        dividends,
+        splits,
+    };
+
+    // Intraday charts come without adjclose; rebuild it from the dividends
+    if !has_adj_closes && !data.dividends.is_empty() {
+        data.rebuild_adj_closes();
+    }
+
+    Ok(data)
+}
+
+/// Quote from a `range=1d` chart: the meta fields, falling back to the
+/// day's bar when Yahoo leaves them out. `now` is Unix time.
+pub fn parse_quote(ticker: &str, result: YahooResult, now: i64) -> Result<QuoteSnapshot, AppError> {
+    quote_from_meta(ticker, &result.meta, result.indicators.quote.first(), now)
+}
+
+/// Quotes for `tickers` (as the user typed them) from one spark response,
+/// in order. Symbols Yahoo left out are `NotFound`.
+pub fn parse_spark(tickers: &[String], response: YahooSparkResponse, now: i64) -> Vec<Result<QuoteSnapshot, AppError>> {
+    let metas: HashMap<String, YahooMeta> = response.spark.result
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|r| Some((r.symbol, r.response.into_iter().next()?.meta)))
+        .collect();
+
+    tickers
+        .iter()
+        .map(|ticker| {
+            let symbol = Symbol::parse(ticker)?.yahoo();
+            let meta = metas.get(&symbol)
+                .ok_or_else(|| AppError::NotFound(format!("No quote for {}", ticker)))?;
+            quote_from_meta(ticker, meta, None, now)
+        })
+        .collect()
+}
+
+/// `bar` is the day's bar, used where the meta leaves a field out
+fn quote_from_meta(ticker: &str, meta: &YahooMeta, bar: Option<&YahooQuote>, now: i64) -> Result<QuoteSnapshot, AppError> {
+    let price = meta.regular_market_price
+        .ok_or_else(|| AppError::NotFound(format!("No market price for {}", ticker)))?;
+    let previous_close = meta.previous_close.or(meta.chart_previous_close).filter(|c| *c > 0.0);
+
+    let last = |series: &[Option<f64>]| series.last().copied().flatten();
+    let day_high = meta.regular_market_day_high.or_else(|| bar.and_then(|q| last(&q.high)));
+    let day_low = meta.regular_market_day_low.or_else(|| bar.and_then(|q| last(&q.low)));
+    let volume = meta.regular_market_volume.or_else(|| bar.and_then(|q| q.volume.last().copied().flatten()));
+
+    let offset = meta.gmtoffset
+        .and_then(|o| i32::try_from(o).ok())
+        .and_then(chrono::FixedOffset::east_opt)
+        .unwrap_or(chrono::FixedOffset::east_opt(0).unwrap());
+    let timestamp = meta.regular_market_time
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .map(|dt| dt.with_timezone(&offset));
+
+    Ok(QuoteSnapshot {
+        ticker: ticker.to_string(),
+        price,
+        previous_close,
+        change: previous_close.map(|c| price - c),
+        change_percent: previous_close.map(|c| (price - c) / c * 100.0),
+        day_high,
+        day_low,
+        volume,
+        market_state: market_state(meta, now),
+        currency: meta.currency.clone(),
+        timestamp,
+        source: String::new(),
+    })
+}
+
+/// Session at `now` from `meta.currentTradingPeriod` (today's periods, or
+/// the last trading day's outside of trading days)
+fn market_state(meta: &YahooMeta, now: i64) -> MarketState {
+    let Some(periods) = &meta.current_trading_period else {
+        return MarketState::Closed;
+    };
+    let within = |period: &Option<YahooPeriod>| period.as_ref().is_some_and(|p| now >= p.start && now < p.end);
+
+    if within(&periods.regular) {
+        MarketState::Regular
+    } else if within(&periods.pre) {
+        MarketState::Pre
+    } else if within(&periods.post) {
+        MarketState::Post
+    } else {
+        MarketState::Closed
+    }
+}
+
+/// Dividends and splits from the chart `events` block, oldest first
+fn parse_events(
+    events: Option<&YahooEvents>,
+    offset: chrono::FixedOffset,
+) -> Result<(Vec<Dividend>, Vec<Split>), AppError> {
+    let Some(events) = events else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+    let event_time = |ts: i64| {
+        chrono::DateTime::from_timestamp(ts, 0)
+            .map(|dt| dt.with_timezone(&offset))
+            .ok_or_else(|| AppError::Parse(format!("Invalid event timestamp {}", ts)))
+    };
+
+    let mut dividends = events.dividends.iter().flatten()
+        .map(|(_, d)| Ok(Dividend { date: event_time(d.date)?, amount: d.amount }))
+        .collect::<Result<Vec<_>, AppError>>()?;
+    dividends.sort_by_key(|d| d.date);
+
+    let mut splits = events.splits.iter().flatten()
+        .filter(|(_, s)| s.numerator > 0.0 && s.denominator > 0.0)
+        .map(|(_, s)| Ok(Split {
+            date: event_time(s.date)?,
+            numerator: s.numerator,
+            denominator: s.denominator,
+        }))
+        .collect::<Result<Vec<_>, AppError>>()?;
+    splits.sort_by_key(|s| s.date);
+
+    Ok((dividends, splits))
+}
+
+/// Regular-hours `(start, end)` periods from `meta.tradingPeriods`
+fn regular_periods(meta: &YahooMeta) -> Vec<(i64, i64)> {
+    let Some(periods) = &meta.trading_periods else {
+        return Vec::new();
+    };
+    let regular = periods.get("regular").unwrap_or(periods);
+
+    regular
+        .as_array()
+        .map(|days| {
+            days.iter()
+                .filter_map(|day| day.as_array())
+                .flatten()
+                .filter_map(|p| Some((p.get("start")?.as_i64()?, p.get("end")?.as_i64()?)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Tag each bar as pre-market, regular or post-market.
+///
+/// Uses the per-day regular periods when Yahoo sends them, otherwise the
+/// regular session's local time of day from `currentTradingPeriod`.
+fn classify_sessions(timestamps: &[i64], meta: &YahooMeta) -> Vec<BarSession> {
+    let periods = regular_periods(meta);
+    let offset = meta.gmtoffset.unwrap_or(0);
+    let time_of_day = |ts: i64| (ts + offset).rem_euclid(86_400);
+
+    let hours = periods.first().copied().or_else(|| {
+        meta.current_trading_period
+            .as_ref()
+            .and_then(|p| p.regular.as_ref())
+            .map(|r| (r.start, r.end))
+    });
+    let Some((open, close)) = hours else {
+        return vec![BarSession::Regular; timestamps.len()];
+    };
+    let (open_tod, close_tod) = (time_of_day(open), time_of_day(close));
+
+    timestamps.iter().map(|&ts| {
+        let tod = time_of_day(ts);
+        let in_regular = if periods.is_empty() {
+            tod >= open_tod && tod < close_tod
+        } else {
+            periods.iter().any(|&(start, end)| ts >= start && ts < end)
+        };
+
+        if in_regular {
+            BarSession::Regular
+        } else if tod < open_tod {
+            BarSession::Pre
+        } else {
+            BarSession::Post
+        }
+    }).collect()
+}
+
+#[async_trait]
+impl DataProvider for YahooProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            history: true,
+            quotes: true,
+            adjusted_close: true,
+            search: true,
+        }
+    }
+
+    async fn fetch_history(&self, ticker: &str, request: &HistoryRequest) -> Result<HistoricalData, AppError> {
+        let query = if request.start.is_none() && request.end.is_none() {
+            format!(
+                "range={}&interval={}&includeAdjustedClose=true&events=div,splits",
+                request.range.as_str(),
+                request.interval.as_str()
+            )
+        } else {
+            let today = chrono::Utc::now().date_naive();
+            let period1 = request
+                .window_start(today)
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp())
+                .unwrap_or(0);
+            // period2 is exclusive, so include the whole end day
+            let period2 = request
+                .end
+                .map(|d| (d + chrono::Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp())
+                .unwrap_or_else(|| chrono::Utc::now().timestamp());
+            format!(
+                "period1={}&period2={}&interval={}&includeAdjustedClose=true&events=div,splits",
+                period1,
+                period2,
+                request.interval.as_str()
+            )
+        };
+        let query = if request.include_extended_hours && request.interval.is_intraday() {
+            format!("{}&includePrePost=true", query)
+        } else {
+            query
+        };
+
+        let result = self.fetch_chart(ticker, &query).await?;
+        let mut data = parse_chart_result(result, request.interval)?;
+        data.source = self.name.clone();
+        Ok(data)
+    }
+
+    async fn fetch_quote(&self, ticker: &str) -> Result<QuoteSnapshot, AppError> {
+        let result = self.fetch_chart(ticker, "range=1d&interval=1d").await?;
+        let mut quote = parse_quote(ticker, result, chrono::Utc::now().timestamp())?;
+        quote.source = self.name.clone();
+        Ok(quote)
+    }
+
+    /// One spark request per `SPARK_MAX_SYMBOLS` tickers instead of a chart each.
+    /// A failed request fails only its own chunk's tickers.
+    async fn fetch_quotes(&self, tickers: &[String]) -> Result<Vec<Result<QuoteSnapshot, AppError>>, AppError> {
+        let mut quotes = Vec::with_capacity(tickers.len());
+
+        for chunk in tickers.chunks(SPARK_MAX_SYMBOLS) {
+            let symbols: Vec<String> = chunk.iter()
+                .filter_map(|ticker| Symbol::parse(ticker).ok())
+                .map(|symbol| symbol.yahoo())
+                .collect();
+            // Nothing valid to ask for: every entry becomes its parse error
+            let response = if symbols.is_empty() {
+                YahooSparkResponse::default()
+            } else {
+                match self.spark(&symbols).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        println!("[Rust] Quotes failed for {}: {}", symbols.join(","), e);
+                        quotes.extend(chunk.iter().map(|_| Err(e.clone())));
+                        continue;
+                    }
+                }
+            };
+
+            let now = chrono::Utc::now().timestamp();
+            quotes.extend(parse_spark(chunk, response, now).into_iter().map(|quote| {
+                quote.map(|mut quote| {
+                    quote.source = self.name.clone();
+                    quote
+                })
+            }));
+        }
+
+        Ok(quotes)
+    }
+
+    async fn search_symbols(&self, query: &str) -> Result<Vec<SymbolMatch>, AppError> {
+        self.search(query).await
+    }
+}
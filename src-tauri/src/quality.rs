@@ -0,0 +1,309 @@
+use crate::models::{
+    DataQualityReport, GapPolicy, HistoricalData, HistoryRequest, Interval, IssueKind, IssueSeverity,
+    ValidationIssue,
+};
+use chrono::Datelike;
+
+// ==========================================
+// Missing Bars (Gap Policy)
+// ==========================================
+
+/// Apply `policy` to bars flagged as missing and attach a quality report.
+///
+/// Providers return missing bars as NaN + `missing` flag; this is the single
+/// place where they are dropped, filled or kept, so every series (OHLC,
+/// adjusted close, volume) is treated the same way.
+pub fn apply_gap_policy(data: &HistoricalData, policy: GapPolicy, interval: Interval) -> HistoricalData {
+    let len = data.dates.len();
+    let null_bars = (0..len).filter(|&i| data.is_missing(i)).count();
+
+    let (mut result, dropped_bars, filled_bars) = match policy {
+        GapPolicy::Drop => (data.without_missing(), null_bars, 0),
+        GapPolicy::MarkMissing => (data.clone(), 0, 0),
+        GapPolicy::ForwardFill => forward_fill(data),
+    };
+
+    let zero_volume_bars = (0..len)
+        .filter(|&i| !data.is_missing(i) && data.volumes.get(i) == Some(&0))
+        .count();
+
+    let calendar_gaps = if interval == Interval::OneDay {
+        count_weekday_gaps(data)
+    } else {
+        0
+    };
+
+    result.quality = Some(DataQualityReport {
+        gap_policy: policy,
+        bars: result.dates.len(),
+        null_bars,
+        dropped_bars,
+        filled_bars,
+        calendar_gaps,
+        zero_volume_bars,
+        issues: Vec::new(),
+    });
+    result
+}
+
+/// Replace missing bars with a flat bar at the previous close and zero volume.
+/// Leading missing bars have nothing to fill from and are dropped.
+fn forward_fill(data: &HistoricalData) -> (HistoricalData, usize, usize) {
+    let first_real = (0..data.dates.len()).find(|&i| !data.is_missing(i));
+    let Some(first_real) = first_real else {
+        let dropped = data.dates.len();
+        return (data.without_missing(), dropped, 0);
+    };
+
+    let keep: Vec<usize> = (first_real..data.dates.len()).collect();
+    let mut filled = data.select(&keep);
+    let mut filled_bars = 0;
+
+    let n = filled.dates.len()
+        .min(filled.opens.len())
+        .min(filled.highs.len())
+        .min(filled.lows.len())
+        .min(filled.closes.len())
+        .min(filled.adj_closes.len())
+        .min(filled.volumes.len());
+
+    for i in 1..n {
+        if !filled.is_missing(i) {
+            continue;
+        }
+        let prev_close = filled.closes[i - 1];
+        let prev_adj = filled.adj_closes[i - 1];
+        filled.opens[i] = prev_close;
+        filled.highs[i] = prev_close;
+        filled.lows[i] = prev_close;
+        filled.closes[i] = prev_close;
+        filled.adj_closes[i] = prev_adj;
+        filled.volumes[i] = 0;
+        filled_bars += 1;
+    }
+
+    // Every bar now has prices
+    filled.missing = Vec::new();
+    (filled, first_real, filled_bars)
+}
+
+/// Weekdays strictly between consecutive bars that have no bar of their own
+fn count_weekday_gaps(data: &HistoricalData) -> usize {
+    let mut gaps = 0;
+    for i in 1..data.dates.len() {
+        let (Some(prev), Some(curr)) = (data.trading_day(i - 1), data.trading_day(i)) else {
+            continue;
+        };
+        let mut day = prev.succ_opt();
+        while let Some(d) = day.filter(|d| *d < curr) {
+            if d.weekday().num_days_from_monday() < 5 {
+                gaps += 1;
+            }
+            day = d.succ_opt();
+        }
+    }
+    gaps
+}
+
+// ==========================================
+// Sanity Validation
+// ==========================================
+
+const MAX_DAILY_MOVE: f64 = 0.5; // >50% bar-to-bar close change is suspicious
+const STALE_WEEKDAYS: usize = 3;
+
+/// Weekdays a bar may lag today before it counts as stale. Weekly and monthly
+/// bars are dated at the start of their period, so the period itself is allowed
+/// on top (up to 5 weekdays in a week, 23 in a month).
+fn stale_limit(interval: Interval) -> usize {
+    match interval {
+        Interval::OneWeek => 5 + STALE_WEEKDAYS,
+        Interval::OneMonth => 23 + STALE_WEEKDAYS,
+        interval if interval.is_intraday() => 1,
+        _ => STALE_WEEKDAYS,
+    }
+}
+
+/// Check a fetched series for malformed or implausible bars.
+///
+/// Problems that make indicators meaningless (mismatched series lengths,
+/// non-positive prices) are errors; the rest are warnings. Bars flagged as
+/// missing are skipped.
+pub fn validate_history(data: &HistoricalData, request: &HistoryRequest) -> Vec<ValidationIssue> {
+    validate_history_on(data, request, chrono::Utc::now().date_naive())
+}
+
+/// `validate_history` as of `today`
+fn validate_history_on(
+    data: &HistoricalData,
+    request: &HistoryRequest,
+    today: chrono::NaiveDate,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    if !data.lengths_match() {
+        issues.push(ValidationIssue {
+            kind: IssueKind::LengthMismatch,
+            severity: IssueSeverity::Error,
+            count: 1,
+            first_date: None,
+            message: format!(
+                "Series lengths differ (dates {}, open {}, high {}, low {}, close {}, adj close {}, volume {})",
+                data.dates.len(),
+                data.opens.len(),
+                data.highs.len(),
+                data.lows.len(),
+                data.closes.len(),
+                data.adj_closes.len(),
+                data.volumes.len()
+            ),
+        });
+        // Per-bar checks below would compare unrelated bars
+        return issues;
+    }
+
+    let bars: Vec<usize> = (0..data.dates.len()).filter(|&i| !data.is_missing(i)).collect();
+
+    let mut check = |kind: IssueKind, severity: IssueSeverity, what: &str, hits: Vec<usize>| {
+        if let Some(&first) = hits.first() {
+            issues.push(ValidationIssue {
+                kind,
+                severity,
+                count: hits.len(),
+                first_date: data.dates.get(first).copied(),
+                message: format!("{} bar(s) with {}", hits.len(), what),
+            });
+        }
+    };
+
+    check(
+        IssueKind::NonPositivePrice,
+        IssueSeverity::Error,
+        "zero or negative prices",
+        bars.iter().copied().filter(|&i| {
+            [data.opens[i], data.highs[i], data.lows[i], data.closes[i], data.adj_closes[i]]
+                .iter()
+                .any(|&p| p <= 0.0)
+        }).collect(),
+    );
+
+    check(
+        IssueKind::HighBelowLow,
+        IssueSeverity::Warning,
+        "high below low",
+        bars.iter().copied().filter(|&i| data.highs[i] < data.lows[i]).collect(),
+    );
+
+    let tolerance = |p: f64| p.abs() * 1e-6;
+    check(
+        IssueKind::CloseOutsideRange,
+        IssueSeverity::Warning,
+        "close outside the high/low range",
+        bars.iter().copied().filter(|&i| {
+            let close = data.closes[i];
+            close < data.lows[i] - tolerance(close) || close > data.highs[i] + tolerance(close)
+        }).collect(),
+    );
+
+    // Closes are split-adjusted, so a real move this large is rare; a jump
+    // on a split date means the provider has not adjusted yet
+    check(
+        IssueKind::SuspiciousJump,
+        IssueSeverity::Warning,
+        "a >50% close-to-close move",
+        bars.windows(2).filter_map(|w| {
+            let (prev, curr) = (data.closes[w[0]], data.closes[w[1]]);
+            let jump = prev > 0.0 && (curr / prev - 1.0).abs() > MAX_DAILY_MOVE;
+            (jump && !data.is_split_day(w[1])).then_some(w[1])
+        }).collect(),
+    );
+
+    // A historical window is expected to end in the past
+    if request.end.is_none() {
+        if let Some(last) = bars.last().and_then(|&i| data.trading_day(i)) {
+            let limit = stale_limit(request.interval);
+            let behind = weekdays_between(last, today);
+            if behind > limit {
+                issues.push(ValidationIssue {
+                    kind: IssueKind::StaleData,
+                    severity: IssueSeverity::Warning,
+                    count: 1,
+                    first_date: data.dates.get(*bars.last().unwrap()).copied(),
+                    message: format!("Last bar is {} weekday(s) old ({})", behind, last),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Weekdays after `from` up to and including `to`
+fn weekdays_between(from: chrono::NaiveDate, to: chrono::NaiveDate) -> usize {
+    let mut count = 0;
+    let mut day = from.succ_opt();
+    while let Some(d) = day.filter(|d| *d <= to) {
+        if d.weekday().num_days_from_monday() < 5 {
+            count += 1;
+        }
+        day = d.succ_opt();
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::daily_bar_time;
+
+    fn day(date: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    /// Flat bars on `dates`
+    fn series(dates: &[&str]) -> HistoricalData {
+        let n = dates.len();
+        HistoricalData {
+            dates: dates.iter().map(|d| daily_bar_time(day(d))).collect(),
+            opens: vec![100.0; n],
+            highs: vec![101.0; n],
+            lows: vec![99.0; n],
+            closes: vec![100.0; n],
+            adj_closes: vec![100.0; n],
+            volumes: vec![1_000; n],
+            ..Default::default()
+        }
+    }
+
+    fn stale(data: &HistoricalData, interval: Interval, today: &str) -> bool {
+        let request = HistoryRequest { interval, ..Default::default() };
+        validate_history_on(data, &request, day(today))
+            .iter()
+            .any(|issue| issue.kind == IssueKind::StaleData)
+    }
+
+    #[test]
+    fn current_weekly_series_is_not_stale() {
+        // Weekly bars are dated Monday; on Friday the latest is still this week's
+        let weekly = series(&["2024-09-16", "2024-09-23", "2024-09-30"]);
+        assert!(!stale(&weekly, Interval::OneWeek, "2024-10-04"));
+        // Next week's bar is due from Monday; a few days' grace beyond that
+        assert!(!stale(&weekly, Interval::OneWeek, "2024-10-09"));
+        assert!(stale(&weekly, Interval::OneWeek, "2024-10-14"));
+    }
+
+    #[test]
+    fn current_monthly_series_is_not_stale() {
+        let monthly = series(&["2024-08-01", "2024-09-02", "2024-10-01"]);
+        assert!(!stale(&monthly, Interval::OneMonth, "2024-10-31"));
+        assert!(stale(&monthly, Interval::OneMonth, "2024-11-08"));
+    }
+
+    #[test]
+    fn daily_series_goes_stale_after_three_weekdays() {
+        let daily = series(&["2024-10-02", "2024-10-03", "2024-10-04"]);
+        // Friday's bar across a weekend
+        assert!(!stale(&daily, Interval::OneDay, "2024-10-09"));
+        assert!(stale(&daily, Interval::OneDay, "2024-10-10"));
+    }
+}
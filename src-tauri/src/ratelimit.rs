@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// ==========================================
+// Per-Host Rate Limiting
+// ==========================================
+
+/// Requests allowed in a burst and sustained requests per second for a host
+#[derive(Debug, Clone, Copy)]
+pub struct HostLimit {
+    pub burst: f64,
+    pub per_second: f64,
+}
+
+const DEFAULT_LIMIT: HostLimit = HostLimit { burst: 4.0, per_second: 2.0 };
+
+/// Longest `Retry-After` we are willing to wait out inside a single call
+pub const MAX_RETRY_WAIT_SECS: u64 = 30;
+
+const BACKOFF_BASE_MS: u64 = 500;
+const BACKOFF_CAP_MS: u64 = 8_000;
+
+struct Bucket {
+    limit: HostLimit,
+    tokens: f64,
+    refilled_at: Instant,
+    /// Set from `Retry-After`; no request goes out before this
+    paused_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(limit: HostLimit) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst,
+            refilled_at: Instant::now(),
+            paused_until: None,
+        }
+    }
+
+    /// Take a token, or say how long to wait before trying again
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.paused_until {
+            if now < until {
+                return Err(until - now);
+            }
+            self.paused_until = None;
+        }
+
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.per_second).min(self.limit.burst);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.limit.per_second))
+        }
+    }
+}
+
+/// Token buckets keyed by host, shared by every provider and command.
+///
+/// Because all outbound calls to a host draw from the same bucket, concurrent
+/// batch commands cannot add up to more than the host's limit.
+pub struct RateLimiter {
+    limits: HashMap<String, HostLimit>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: HashMap<String, HostLimit>) -> Self {
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait until a request to `host` is allowed
+    pub async fn acquire(&self, host: &str) {
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let limit = self.limits.get(host).copied().unwrap_or(DEFAULT_LIMIT);
+                let bucket = buckets
+                    .entry(host.to_string())
+                    .or_insert_with(|| Bucket::new(limit));
+                match bucket.try_take(Instant::now()) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Hold back every request to `host` for `duration` (server sent Retry-After)
+    pub fn pause(&self, host: &str, duration: Duration) {
+        let mut buckets = self.buckets.lock().unwrap();
+        let limit = self.limits.get(host).copied().unwrap_or(DEFAULT_LIMIT);
+        let bucket = buckets
+            .entry(host.to_string())
+            .or_insert_with(|| Bucket::new(limit));
+        let until = Instant::now() + duration;
+        bucket.paused_until = Some(bucket.paused_until.map_or(until, |u| u.max(until)));
+        // Resume with an empty bucket rather than a burst
+        bucket.tokens = 0.0;
+    }
+}
+
+/// Delay before retry number `retry` (1-based): exponential, jittered so
+/// concurrent callers do not retry in lockstep
+pub fn backoff_delay(retry: u32) -> Duration {
+    let ceiling = BACKOFF_BASE_MS
+        .saturating_mul(1 << retry.saturating_sub(1).min(16))
+        .min(BACKOFF_CAP_MS);
+    // Never retry immediately: at least half of the ceiling
+    let jittered = ceiling / 2 + rand::random::<u64>() % (ceiling / 2 + 1);
+    Duration::from_millis(jittered)
+}
+
+/// `Retry-After` in seconds (the HTTP-date form is not used by our providers)
+pub fn retry_after_secs(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
@@ -0,0 +1,318 @@
+use crate::error::AppError;
+use crate::models::{Dividend, HistoricalData, Split};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::Mutex;
+
+// ==========================================
+// Persistent History Store (SQLite)
+// ==========================================
+
+const SCHEMA_VERSION: i64 = 5;
+
+/// `series` row: source, timezone, gmtoffset, currency
+type SeriesRow = (String, Option<String>, Option<i32>, Option<String>);
+
+/// On-disk bar store keyed by ticker + interval, one row per trading day.
+///
+/// Survives restarts so a launch only needs to download the bars after the
+/// last stored date. Calls are short and synchronous.
+pub struct HistoryStore {
+    conn: Mutex<Connection>,
+}
+
+impl HistoryStore {
+    pub fn open(path: &Path) -> Result<Self, AppError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::Storage(format!("dir: {}", e)))?;
+        }
+
+        let conn = Connection::open(path).map_err(|e| AppError::Storage(format!("open: {}", e)))?;
+
+        // The store is only a cache: older layouts are dropped and re-downloaded
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| AppError::Storage(format!("schema: {}", e)))?;
+        if version < SCHEMA_VERSION {
+            conn.execute_batch("DROP TABLE IF EXISTS bars; DROP TABLE IF EXISTS series; DROP TABLE IF EXISTS events;")
+                .map_err(|e| AppError::Storage(format!("schema: {}", e)))?;
+        }
+
+        conn.execute_batch(&format!(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA user_version = {};
+             CREATE TABLE IF NOT EXISTS bars (
+                 ticker    TEXT NOT NULL,
+                 interval  TEXT NOT NULL,
+                 date      TEXT NOT NULL,
+                 time      TEXT NOT NULL,
+                 open      REAL,
+                 high      REAL,
+                 low       REAL,
+                 close     REAL,
+                 adj_close REAL,
+                 volume    INTEGER NOT NULL,
+                 PRIMARY KEY (ticker, interval, date)
+             );
+             CREATE TABLE IF NOT EXISTS series (
+                 ticker     TEXT NOT NULL,
+                 interval   TEXT NOT NULL,
+                 source     TEXT NOT NULL,
+                 timezone   TEXT,
+                 gmtoffset  INTEGER,
+                 currency   TEXT,
+                 updated_at INTEGER NOT NULL,
+                 PRIMARY KEY (ticker, interval)
+             );
+             CREATE TABLE IF NOT EXISTS events (
+                 ticker      TEXT NOT NULL,
+                 kind        TEXT NOT NULL,
+                 date        TEXT NOT NULL,
+                 time        TEXT NOT NULL,
+                 amount      REAL,
+                 numerator   REAL,
+                 denominator REAL,
+                 PRIMARY KEY (ticker, kind, date)
+             );",
+            SCHEMA_VERSION
+        ))
+        .map_err(|e| AppError::Storage(format!("schema: {}", e)))?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// All stored bars for a series, oldest first
+    pub fn load(&self, ticker: &str, interval: &str) -> Result<Option<HistoricalData>, AppError> {
+        let conn = self.conn.lock().unwrap();
+
+        let series: Option<SeriesRow> = conn
+            .query_row(
+                "SELECT source, timezone, gmtoffset, currency FROM series WHERE ticker = ?1 AND interval = ?2",
+                params![ticker, interval],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()
+            .map_err(|e| AppError::Storage(format!("read: {}", e)))?;
+
+        let Some((source, timezone, gmtoffset, currency)) = series else {
+            return Ok(None);
+        };
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT time, open, high, low, close, adj_close, volume FROM bars
+                 WHERE ticker = ?1 AND interval = ?2 ORDER BY date",
+            )
+            .map_err(|e| AppError::Storage(format!("read: {}", e)))?;
+
+        let mut data = HistoricalData {
+            source,
+            timezone,
+            gmtoffset,
+            currency,
+            ..Default::default()
+        };
+
+        let mut missing = Vec::new();
+        let mut rows = stmt
+            .query(params![ticker, interval])
+            .map_err(|e| AppError::Storage(format!("read: {}", e)))?;
+        while let Some(row) = rows.next().map_err(|e| AppError::Storage(format!("read: {}", e)))? {
+            let read = |e: rusqlite::Error| AppError::Storage(format!("read: {}", e));
+            let time: String = row.get(0).map_err(read)?;
+            data.dates.push(
+                chrono::DateTime::parse_from_rfc3339(&time)
+                    .map_err(|e| AppError::Storage(format!("read: bad time '{}' ({})", time, e)))?,
+            );
+            // Missing bars are stored as NULL prices
+            let prices: [Option<f64>; 5] = [
+                row.get(1).map_err(read)?,
+                row.get(2).map_err(read)?,
+                row.get(3).map_err(read)?,
+                row.get(4).map_err(read)?,
+                row.get(5).map_err(read)?,
+            ];
+            missing.push(prices.iter().any(|p| p.is_none()));
+            data.opens.push(prices[0].unwrap_or(f64::NAN));
+            data.highs.push(prices[1].unwrap_or(f64::NAN));
+            data.lows.push(prices[2].unwrap_or(f64::NAN));
+            data.closes.push(prices[3].unwrap_or(f64::NAN));
+            data.adj_closes.push(prices[4].unwrap_or(f64::NAN));
+            data.volumes.push(row.get::<_, i64>(6).map_err(read)? as u64);
+        }
+
+        if data.dates.is_empty() {
+            return Ok(None);
+        }
+        if missing.contains(&true) {
+            data.missing = missing;
+        }
+
+        // Corporate actions are per ticker, shared by every interval
+        let mut stmt = conn
+            .prepare(
+                "SELECT kind, time, amount, numerator, denominator FROM events
+                 WHERE ticker = ?1 ORDER BY date",
+            )
+            .map_err(|e| AppError::Storage(format!("read: {}", e)))?;
+        let mut rows = stmt
+            .query(params![ticker])
+            .map_err(|e| AppError::Storage(format!("read: {}", e)))?;
+        while let Some(row) = rows.next().map_err(|e| AppError::Storage(format!("read: {}", e)))? {
+            let read = |e: rusqlite::Error| AppError::Storage(format!("read: {}", e));
+            let kind: String = row.get(0).map_err(read)?;
+            let time: String = row.get(1).map_err(read)?;
+            let date = chrono::DateTime::parse_from_rfc3339(&time)
+                .map_err(|e| AppError::Storage(format!("read: bad time '{}' ({})", time, e)))?;
+            match kind.as_str() {
+                "dividend" => data.dividends.push(Dividend {
+                    date,
+                    amount: row.get::<_, Option<f64>>(2).map_err(read)?.unwrap_or(0.0),
+                }),
+                "split" => data.splits.push(Split {
+                    date,
+                    numerator: row.get::<_, Option<f64>>(3).map_err(read)?.unwrap_or(1.0),
+                    denominator: row.get::<_, Option<f64>>(4).map_err(read)?.unwrap_or(1.0),
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(Some(data))
+    }
+
+    /// Forget everything stored for `ticker` (all intervals and events)
+    pub fn delete_ticker(&self, ticker: &str) -> Result<(), AppError> {
+        let mut conn = self.conn.lock().unwrap();
+        let write = |e: rusqlite::Error| AppError::Storage(format!("write: {}", e));
+        let tx = conn.transaction().map_err(write)?;
+        for table in ["bars", "series", "events"] {
+            tx.execute(&format!("DELETE FROM {} WHERE ticker = ?1", table), params![ticker])
+                .map_err(write)?;
+        }
+        tx.commit().map_err(write)
+    }
+
+    /// Forget every stored series
+    pub fn clear(&self) -> Result<(), AppError> {
+        let mut conn = self.conn.lock().unwrap();
+        let write = |e: rusqlite::Error| AppError::Storage(format!("write: {}", e));
+        let tx = conn.transaction().map_err(write)?;
+        for table in ["bars", "series", "events"] {
+            tx.execute(&format!("DELETE FROM {}", table), []).map_err(write)?;
+        }
+        tx.commit().map_err(write)
+    }
+
+    /// Insert or overwrite bars by date, keeping older stored bars
+    pub fn merge(&self, ticker: &str, interval: &str, data: &HistoricalData) -> Result<(), AppError> {
+        self.write(ticker, interval, data, false)
+    }
+
+    /// Drop the stored series and store `data` instead (history was restated)
+    pub fn replace(&self, ticker: &str, interval: &str, data: &HistoricalData) -> Result<(), AppError> {
+        self.write(ticker, interval, data, true)
+    }
+
+    fn write(&self, ticker: &str, interval: &str, data: &HistoricalData, replace: bool) -> Result<(), AppError> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().map_err(|e| AppError::Storage(format!("write: {}", e)))?;
+        let write = |e: rusqlite::Error| AppError::Storage(format!("write: {}", e));
+
+        if replace {
+            tx.execute(
+                "DELETE FROM bars WHERE ticker = ?1 AND interval = ?2",
+                params![ticker, interval],
+            )
+            .map_err(write)?;
+            tx.execute("DELETE FROM events WHERE ticker = ?1", params![ticker])
+                .map_err(write)?;
+        }
+
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT OR REPLACE INTO bars
+                     (ticker, interval, date, time, open, high, low, close, adj_close, volume)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                )
+                .map_err(write)?;
+
+            let len = data.dates.len()
+                .min(data.opens.len())
+                .min(data.highs.len())
+                .min(data.lows.len())
+                .min(data.closes.len())
+                .min(data.adj_closes.len())
+                .min(data.volumes.len());
+
+            for i in 0..len {
+                let price = |v: f64| if data.is_missing(i) || v.is_nan() { None } else { Some(v) };
+                stmt.execute(params![
+                    ticker,
+                    interval,
+                    data.dates[i].date_naive().format("%Y-%m-%d").to_string(),
+                    data.dates[i].to_rfc3339(),
+                    price(data.opens[i]),
+                    price(data.highs[i]),
+                    price(data.lows[i]),
+                    price(data.closes[i]),
+                    price(data.adj_closes[i]),
+                    data.volumes[i] as i64,
+                ])
+                .map_err(write)?;
+            }
+
+            let mut stmt = tx
+                .prepare(
+                    "INSERT OR REPLACE INTO events
+                     (ticker, kind, date, time, amount, numerator, denominator)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                )
+                .map_err(write)?;
+            for d in &data.dividends {
+                stmt.execute(params![
+                    ticker,
+                    "dividend",
+                    d.date.date_naive().format("%Y-%m-%d").to_string(),
+                    d.date.to_rfc3339(),
+                    d.amount,
+                    None::<f64>,
+                    None::<f64>,
+                ])
+                .map_err(write)?;
+            }
+            for s in &data.splits {
+                stmt.execute(params![
+                    ticker,
+                    "split",
+                    s.date.date_naive().format("%Y-%m-%d").to_string(),
+                    s.date.to_rfc3339(),
+                    None::<f64>,
+                    s.numerator,
+                    s.denominator,
+                ])
+                .map_err(write)?;
+            }
+        }
+
+        tx.execute(
+            "INSERT OR REPLACE INTO series (ticker, interval, source, timezone, gmtoffset, currency, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                ticker,
+                interval,
+                data.source,
+                data.timezone,
+                data.gmtoffset,
+                data.currency,
+                chrono::Utc::now().timestamp()
+            ],
+        )
+        .map_err(write)?;
+
+        tx.commit().map_err(write)
+    }
+}
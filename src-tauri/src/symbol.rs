@@ -0,0 +1,431 @@
+use crate::error::AppError;
+
+// ==========================================
+// Ticker Symbols
+// ==========================================
+
+/// Listing venue of a symbol, from its Yahoo-style suffix (`.KS`, `.T`, ...)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Exchange {
+    /// No suffix: NYSE, Nasdaq, NYSE Arca, ...
+    Us,
+    Korea,
+    Kosdaq,
+    Tokyo,
+    Toronto,
+    TsxVenture,
+    London,
+    Xetra,
+    Frankfurt,
+    Paris,
+    Amsterdam,
+    Brussels,
+    Milan,
+    Madrid,
+    Swiss,
+    Stockholm,
+    Oslo,
+    Copenhagen,
+    Helsinki,
+    HongKong,
+    Shanghai,
+    Shenzhen,
+    Taiwan,
+    Australia,
+    /// Index (`^GSPC`)
+    Index,
+    /// Currency pair (`USDKRW=X`)
+    Fx,
+    /// Continuous futures contract (`GC=F`)
+    Futures,
+    /// Any other Yahoo suffix; passed through as is
+    Other(String),
+}
+
+/// Yahoo suffix per exchange (single letters are exchanges, not share classes)
+const SUFFIXES: &[(&str, Exchange)] = &[
+    ("KS", Exchange::Korea),
+    ("KQ", Exchange::Kosdaq),
+    ("T", Exchange::Tokyo),
+    ("TO", Exchange::Toronto),
+    ("V", Exchange::TsxVenture),
+    ("L", Exchange::London),
+    ("DE", Exchange::Xetra),
+    ("F", Exchange::Frankfurt),
+    ("PA", Exchange::Paris),
+    ("AS", Exchange::Amsterdam),
+    ("BR", Exchange::Brussels),
+    ("MI", Exchange::Milan),
+    ("MC", Exchange::Madrid),
+    ("SW", Exchange::Swiss),
+    ("ST", Exchange::Stockholm),
+    ("OL", Exchange::Oslo),
+    ("CO", Exchange::Copenhagen),
+    ("HE", Exchange::Helsinki),
+    ("HK", Exchange::HongKong),
+    ("SS", Exchange::Shanghai),
+    ("SZ", Exchange::Shenzhen),
+    ("TW", Exchange::Taiwan),
+    ("AX", Exchange::Australia),
+];
+
+impl Exchange {
+    fn from_suffix(suffix: &str) -> Option<Exchange> {
+        SUFFIXES.iter().find(|(s, _)| *s == suffix).map(|(_, e)| e.clone())
+    }
+
+    /// Trading currency of the exchange's listings (London quotes in pence, `GBp`)
+    pub fn currency(&self) -> Option<&'static str> {
+        let currency = match self {
+            Exchange::Us => "USD",
+            Exchange::Korea | Exchange::Kosdaq => "KRW",
+            Exchange::Tokyo => "JPY",
+            Exchange::Toronto | Exchange::TsxVenture => "CAD",
+            Exchange::London => "GBp",
+            Exchange::Xetra
+            | Exchange::Frankfurt
+            | Exchange::Paris
+            | Exchange::Amsterdam
+            | Exchange::Brussels
+            | Exchange::Milan
+            | Exchange::Madrid
+            | Exchange::Helsinki => "EUR",
+            Exchange::Swiss => "CHF",
+            Exchange::Stockholm => "SEK",
+            Exchange::Oslo => "NOK",
+            Exchange::Copenhagen => "DKK",
+            Exchange::HongKong => "HKD",
+            Exchange::Shanghai | Exchange::Shenzhen => "CNY",
+            Exchange::Taiwan => "TWD",
+            Exchange::Australia => "AUD",
+            Exchange::Index | Exchange::Fx | Exchange::Futures | Exchange::Other(_) => return None,
+        };
+        Some(currency)
+    }
+
+    /// Yahoo suffix without the dot; `None` for US listings and the
+    /// `^`/`=X`/`=F` forms
+    pub fn suffix(&self) -> Option<&str> {
+        match self {
+            Exchange::Other(suffix) => Some(suffix),
+            _ => SUFFIXES.iter().find(|(_, e)| e == self).map(|(s, _)| *s),
+        }
+    }
+}
+
+/// A parsed ticker: `BRK.B` is root `BRK`, class `B` on a US exchange;
+/// `005930.KS` is root `005930` on KRX; `RCI-B.TO` is root `RCI`, class `B`
+/// in Toronto.
+///
+/// Users type tickers in Yahoo style; each provider formats the symbol its
+/// own way (`yahoo()`, `stooq()`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    pub root: String,
+    /// Share class letter (`B` in `BRK.B`, `BRK-B`, `BRK/B`)
+    pub class: Option<String>,
+    pub exchange: Exchange,
+}
+
+fn is_class(part: &str) -> bool {
+    part.len() == 1 && part.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn is_currency_code(part: &str) -> bool {
+    part.len() == 3 && part.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+impl Symbol {
+    pub fn parse(input: &str) -> Result<Symbol, AppError> {
+        let upper = input.trim().to_uppercase();
+        let invalid = |why: &str| AppError::InvalidSymbol(format!("{:?}: {}", input, why));
+
+        if upper.is_empty() {
+            return Err(invalid("empty"));
+        }
+
+        let special = |root: &str, exchange: Exchange| Symbol {
+            root: root.to_string(),
+            class: None,
+            exchange,
+        };
+        let (rest, exchange) = if let Some(root) = upper.strip_prefix('^') {
+            (root, Exchange::Index)
+        } else if let Some(root) = upper.strip_suffix("=X") {
+            (root, Exchange::Fx)
+        } else if let Some(root) = upper.strip_suffix("=F") {
+            (root, Exchange::Futures)
+        } else {
+            match upper.rsplit_once('.') {
+                Some((rest, suffix)) => match Exchange::from_suffix(suffix) {
+                    Some(exchange) => (rest, exchange),
+                    // BRK.B: share class of a US listing
+                    None if is_class(suffix) => (upper.as_str(), Exchange::Us),
+                    None if suffix.len() <= 3 && suffix.chars().all(|c| c.is_ascii_alphabetic()) => {
+                        (rest, Exchange::Other(suffix.to_string()))
+                    }
+                    None => return Err(invalid("unknown exchange suffix")),
+                },
+                None => (upper.as_str(), Exchange::Us),
+            }
+        };
+
+        let valid = |s: &str| {
+            !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '&' | '.' | '/'))
+        };
+        if !valid(rest) {
+            return Err(invalid("unexpected characters"));
+        }
+        if matches!(exchange, Exchange::Index | Exchange::Fx | Exchange::Futures) {
+            return Ok(special(rest, exchange));
+        }
+
+        let (root, class) = match rest.rsplit_once(['.', '-', '/']) {
+            Some((root, class)) if is_class(class) && !root.is_empty() => (root, Some(class.to_string())),
+            _ => (rest, None),
+        };
+        if root.contains(['.', '/']) {
+            return Err(invalid("unexpected characters"));
+        }
+
+        Ok(Symbol {
+            root: root.to_string(),
+            class,
+            exchange,
+        })
+    }
+
+    /// Currency prices are quoted in, when it follows from the symbol: the
+    /// exchange's currency, or the quote side of a pair (`USDKRW=X` and
+    /// `KRW=X` are KRW) or of a crypto ticker (`BTC-USD`)
+    pub fn currency(&self) -> Option<String> {
+        match &self.exchange {
+            Exchange::Fx => {
+                let quote = self.root.get(self.root.len().saturating_sub(3)..)?;
+                is_currency_code(quote).then(|| quote.to_string())
+            }
+            Exchange::Us => Some(self.crypto_quote().unwrap_or("USD").to_string()),
+            exchange => exchange.currency().map(str::to_string),
+        }
+    }
+
+    /// Quote currency of a Yahoo crypto pair (`USD` in `BTC-USD`)
+    fn crypto_quote(&self) -> Option<&str> {
+        if self.exchange != Exchange::Us {
+            return None;
+        }
+        self.root.rsplit_once('-').map(|(_, quote)| quote).filter(|quote| is_currency_code(quote))
+    }
+
+    /// Yahoo Finance symbol: `BRK-B`, `005930.KS`, `RCI-B.TO`, `^GSPC`, `USDKRW=X`
+    pub fn yahoo(&self) -> String {
+        let mut symbol = match self.exchange {
+            Exchange::Index => return format!("^{}", self.root),
+            Exchange::Fx => return format!("{}=X", self.root),
+            Exchange::Futures => return format!("{}=F", self.root),
+            _ => self.root.clone(),
+        };
+        if let Some(class) = &self.class {
+            symbol = format!("{}-{}", symbol, class);
+        }
+        if let Some(suffix) = self.exchange.suffix() {
+            symbol = format!("{}.{}", symbol, suffix);
+        }
+        symbol
+    }
+
+    /// Stooq symbol: `brk-b.us`, `7203.jp`, `usdkrw`. Markets Stooq does not
+    /// carry (KRX, Canada, ...) are `Unsupported`.
+    pub fn stooq(&self) -> Result<String, AppError> {
+        if self.crypto_quote().is_some() {
+            return Err(AppError::Unsupported(format!("Crypto pair {} not supported by Stooq", self)));
+        }
+        let market = match &self.exchange {
+            Exchange::Us => "us",
+            Exchange::Tokyo => "jp",
+            Exchange::London => "uk",
+            Exchange::Xetra | Exchange::Frankfurt => "de",
+            Exchange::HongKong => "hk",
+            Exchange::Fx => return Ok(self.root.to_lowercase()),
+            Exchange::Index => {
+                return Err(AppError::Unsupported(format!("Index {} not supported by Stooq", self)));
+            }
+            other => {
+                return Err(AppError::Unsupported(format!(
+                    "Stooq has no {:?} listings ({})",
+                    other, self
+                )));
+            }
+        };
+
+        let root = match &self.class {
+            Some(class) => format!("{}-{}", self.root, class),
+            None => self.root.clone(),
+        };
+        Ok(format!("{}.{}", root.to_lowercase(), market))
+    }
+}
+
+/// Canonical form used for cache and store keys: `BRK.B`, `005930.KS`, `RCI.B.TO`
+impl std::fmt::Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.exchange {
+            Exchange::Index | Exchange::Fx | Exchange::Futures => return write!(f, "{}", self.yahoo()),
+            _ => write!(f, "{}", self.root)?,
+        }
+        if let Some(class) = &self.class {
+            write!(f, ".{}", class)?;
+        }
+        if let Some(suffix) = self.exchange.suffix() {
+            write!(f, ".{}", suffix)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Symbol {
+        Symbol::parse(input).unwrap_or_else(|e| panic!("{}: {}", input, e))
+    }
+
+    /// Canonical and Yahoo forms both parse back to the same symbol
+    fn assert_round_trips(symbol: &Symbol) {
+        assert_eq!(&parse(&symbol.to_string()), symbol, "canonical {}", symbol);
+        assert_eq!(&parse(&symbol.yahoo()), symbol, "yahoo {}", symbol.yahoo());
+    }
+
+    fn assert_stooq_unsupported(symbol: &Symbol) {
+        match symbol.stooq() {
+            Err(AppError::Unsupported(_)) => {}
+            other => panic!("{}: expected Unsupported, got {:?}", symbol, other),
+        }
+    }
+
+    #[test]
+    fn share_class_spellings_are_one_symbol() {
+        let brk = parse("BRK.B");
+        assert_eq!(brk.root, "BRK");
+        assert_eq!(brk.class.as_deref(), Some("B"));
+        assert_eq!(brk.exchange, Exchange::Us);
+
+        assert_eq!(parse("BRK-B"), brk);
+        assert_eq!(parse("BRK/B"), brk);
+        assert_eq!(parse(" brk.b "), brk);
+
+        assert_eq!(brk.to_string(), "BRK.B");
+        assert_eq!(brk.yahoo(), "BRK-B");
+        assert_eq!(brk.stooq().unwrap(), "brk-b.us");
+        assert_eq!(brk.currency().as_deref(), Some("USD"));
+        assert_round_trips(&brk);
+    }
+
+    #[test]
+    fn korean_listing() {
+        let samsung = parse("005930.KS");
+        assert_eq!(samsung.root, "005930");
+        assert_eq!(samsung.class, None);
+        assert_eq!(samsung.exchange, Exchange::Korea);
+
+        assert_eq!(samsung.to_string(), "005930.KS");
+        assert_eq!(samsung.yahoo(), "005930.KS");
+        assert_eq!(samsung.currency().as_deref(), Some("KRW"));
+        assert_stooq_unsupported(&samsung);
+        assert_round_trips(&samsung);
+    }
+
+    #[test]
+    fn single_letter_exchange_suffix_is_not_a_share_class() {
+        let toyota = parse("7203.T");
+        assert_eq!(toyota.root, "7203");
+        assert_eq!(toyota.class, None);
+        assert_eq!(toyota.exchange, Exchange::Tokyo);
+
+        assert_eq!(toyota.yahoo(), "7203.T");
+        assert_eq!(toyota.stooq().unwrap(), "7203.jp");
+        assert_eq!(toyota.currency().as_deref(), Some("JPY"));
+        assert_round_trips(&toyota);
+
+        assert_eq!(parse("VOD.L").exchange, Exchange::London);
+        assert_eq!(parse("VOD.L").stooq().unwrap(), "vod.uk");
+        assert_eq!(parse("ABC.V").exchange, Exchange::TsxVenture);
+        assert_eq!(parse("BMW.F").exchange, Exchange::Frankfurt);
+    }
+
+    #[test]
+    fn share_class_on_foreign_exchange() {
+        let rogers = parse("RCI-B.TO");
+        assert_eq!(rogers.root, "RCI");
+        assert_eq!(rogers.class.as_deref(), Some("B"));
+        assert_eq!(rogers.exchange, Exchange::Toronto);
+
+        assert_eq!(parse("RCI.B.TO"), rogers);
+        assert_eq!(rogers.to_string(), "RCI.B.TO");
+        assert_eq!(rogers.yahoo(), "RCI-B.TO");
+        assert_eq!(rogers.currency().as_deref(), Some("CAD"));
+        assert_stooq_unsupported(&rogers);
+        assert_round_trips(&rogers);
+    }
+
+    #[test]
+    fn crypto_pair_keeps_its_dash() {
+        let btc = parse("BTC-USD");
+        assert_eq!(btc.root, "BTC-USD");
+        assert_eq!(btc.class, None);
+        assert_eq!(btc.exchange, Exchange::Us);
+
+        assert_eq!(btc.to_string(), "BTC-USD");
+        assert_eq!(btc.yahoo(), "BTC-USD");
+        assert_eq!(btc.currency().as_deref(), Some("USD"));
+        assert_eq!(parse("ETH-KRW").currency().as_deref(), Some("KRW"));
+        assert_stooq_unsupported(&btc);
+        assert_round_trips(&btc);
+    }
+
+    #[test]
+    fn index() {
+        let spx = parse("^GSPC");
+        assert_eq!(spx.root, "GSPC");
+        assert_eq!(spx.exchange, Exchange::Index);
+
+        assert_eq!(spx.to_string(), "^GSPC");
+        assert_eq!(spx.yahoo(), "^GSPC");
+        assert_eq!(spx.currency(), None);
+        assert_stooq_unsupported(&spx);
+        assert_round_trips(&spx);
+    }
+
+    #[test]
+    fn currency_pair() {
+        let usdkrw = parse("USDKRW=X");
+        assert_eq!(usdkrw.root, "USDKRW");
+        assert_eq!(usdkrw.exchange, Exchange::Fx);
+
+        assert_eq!(usdkrw.to_string(), "USDKRW=X");
+        assert_eq!(usdkrw.yahoo(), "USDKRW=X");
+        assert_eq!(usdkrw.stooq().unwrap(), "usdkrw");
+        assert_eq!(usdkrw.currency().as_deref(), Some("KRW"));
+        // Yahoo's short form is quoted against USD
+        assert_eq!(parse("KRW=X").currency().as_deref(), Some("KRW"));
+        assert_round_trips(&usdkrw);
+    }
+
+    #[test]
+    fn unknown_suffixes() {
+        let other = parse("XYZ.ABC");
+        assert_eq!(other.exchange, Exchange::Other("ABC".to_string()));
+        assert_eq!(other.yahoo(), "XYZ.ABC");
+        assert_eq!(other.currency(), None);
+        assert_stooq_unsupported(&other);
+
+        for input in ["", "   ", "XYZ.TOOLONG", "A B", "BRK.B.X.Y", ".KS"] {
+            assert!(
+                matches!(Symbol::parse(input), Err(AppError::InvalidSymbol(_))),
+                "{:?} should be invalid",
+                input
+            );
+        }
+    }
+}
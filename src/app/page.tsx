@@ -3,7 +3,7 @@
 import React, { useState } from 'react';
 import { Settings, Rocket, Play, Pause, Square, RotateCcw, Download, Save, Trash2, ChevronRight, ChevronDown } from 'lucide-react';
 import { Tabs } from '@ark-ui/react';
-import { useMarketData, useTickers, useAnalysis, useAppLifecycle, useSettings } from '@/hooks';
+import { useMarketData, useTickers, useAnalysis, useAppLifecycle, useSettings, useQuotes } from '@/hooks';
 import { MarketIndicators, TickerInput, AnalysisProgress, ResultTable, SettingsModal } from '@/components';
 import { isNativeEnvironment } from '@/lib/utils/platform';
 import type { TabType } from '@/types';
@@ -26,6 +26,8 @@ export default function Home() {
     tickers,
     inputValue,
     setInputValue,
+    inputError,
+    isValidating,
     showAllTickers,
     setShowAllTickers,
     addTicker,
@@ -82,6 +84,9 @@ export default function Home() {
   const bbOnlyResults = results.filter(r => r.bb_touch);
   const currentResults = activeTab === 'triple' ? tripleSignalResults : bbOnlyResults;
 
+  // Live prices for the visible rows (refreshed far more often than the analysis)
+  const quotes = useQuotes(currentResults.filter(r => !r.error).map(r => r.ticker));
+
   return (
     <div className="app-container app-main-layout">
       {/* Settings trigger button */}
@@ -109,6 +114,8 @@ export default function Home() {
         onAdd={addTicker}
         onKeyDown={handleKeyDown}
         isAnalyzing={isAnalyzing}
+        error={inputError}
+        isValidating={isValidating}
       />
 
       {/* Analysis Control Button System */}
@@ -251,6 +258,7 @@ export default function Home() {
         onRemove={handleRemoveTicker}
         settings={settings}
         totalResultsCount={results.filter(r => !r.error).length}
+        quotes={quotes}
       />
 
       {/* Settings Modal */}
@@ -1,7 +1,8 @@
 import React from 'react';
 import { AlertTriangle, RotateCcw, Trash2 } from 'lucide-react';
-import type { AnalysisResult, TabType } from '@/types';
+import type { AnalysisResult, QuoteSnapshot, TabType } from '@/types';
 import type { AnalysisSettings } from '@/types/settings';
+import { formatPrice } from '@/lib/utils/format';
 import '../styles/components/ResultTable.css';
 
 interface ResultTableProps {
@@ -13,9 +14,11 @@ interface ResultTableProps {
     onRemove: (ticker: string) => void;
     settings: AnalysisSettings;
     totalResultsCount: number;
+    /** Live quotes by ticker; override the price from the last analysis */
+    quotes?: Record<string, QuoteSnapshot>;
 }
 
-export function ResultTable({ results, activeTab, isAnalyzing, failedTickers, onRetryFailed, onRemove, settings, totalResultsCount }: ResultTableProps) {
+export function ResultTable({ results, activeTab, isAnalyzing, failedTickers, onRetryFailed, onRemove, settings, totalResultsCount, quotes = {} }: ResultTableProps) {
     if (results.length === 0) return null;
 
     return (
@@ -46,58 +49,87 @@ export function ResultTable({ results, activeTab, isAnalyzing, failedTickers, on
                         </tr>
                     </thead>
                     <tbody>
-                        {results.map((result) => (
-                            <tr key={result.ticker} className={result.alert ? 'alert-row' : ''}>
-                                <td
-                                    className="ticker-name"
-                                    onClick={async (e) => {
-                                        e.preventDefault();
-                                        e.stopPropagation();
-                                        try {
-                                            const { open } = await import('@tauri-apps/plugin-shell');
-                                            await open(`https://www.tossinvest.com/stocks/${result.ticker}`);
-                                        } catch (e) {
-                                            console.error('Failed to open link:', e);
-                                            window.open(`https://www.tossinvest.com/stocks/${result.ticker}`, '_blank');
-                                        }
-                                    }}
-                                    title="토스증권에서 보기"
-                                >
-                                    {result.ticker}
-                                </td>
-                                <td>
-                                    {result.price !== undefined
-                                        ? `$${result.price.toFixed(2)}`
-                                        : '-'}
-                                </td>
-                                <td className={result.rsi !== undefined && result.rsi < settings.rsiTripleSignal ? 'alert-value' : ''}>
-                                    {result.rsi !== undefined ? result.rsi.toFixed(1) : '-'}
-                                </td>
-                                <td className={result.mfi !== undefined && result.mfi < settings.mfiTripleSignal ? 'alert-value' : ''}>
-                                    {result.mfi !== undefined ? result.mfi.toFixed(1) : '-'}
-                                </td>
-                                <td className="md:hidden">
-                                    <span className={result.bb_touch ? 'alert-value' : 'inactive-value'}>
-                                        {result.bb_touch ? '하단👇' : '―'}
-                                    </span>
-                                </td>
-                                <td className="desktop-only">
-                                    <span className={result.bb_touch ? 'alert-value' : 'inactive-value'}>
-                                        {result.bb_touch ? '하단 터치 👇' : '범위 내'}
-                                    </span>
-                                </td>
-                                <td style={{ textAlign: 'center' }}>
-                                    <button
-                                        onClick={() => onRemove(result.ticker)}
-                                        className="remove-btn"
-                                        title="티커 삭제"
-                                        disabled={isAnalyzing}
+                        {results.map((result) => {
+                            const quote = quotes[result.ticker];
+                            const price = quote?.price ?? result.price;
+                            const currency = quote?.currency ?? result.currency;
+                            const changePercent = quote?.changePercent;
+                            // Live prices reuse the rate the analysis was converted at
+                            const converted = result.converted;
+                            const convertedPrice = price !== undefined && converted && converted.currency !== currency
+                                ? price * converted.rate
+                                : undefined;
+                            return (
+                                <tr key={result.ticker} className={result.alert ? 'alert-row' : ''}>
+                                    <td
+                                        className="ticker-name"
+                                        onClick={async (e) => {
+                                            e.preventDefault();
+                                            e.stopPropagation();
+                                            try {
+                                                const { open } = await import('@tauri-apps/plugin-shell');
+                                                await open(`https://www.tossinvest.com/stocks/${result.ticker}`);
+                                            } catch (e) {
+                                                console.error('Failed to open link:', e);
+                                                window.open(`https://www.tossinvest.com/stocks/${result.ticker}`, '_blank');
+                                            }
+                                        }}
+                                        title="토스증권에서 보기"
                                     >
-                                        <Trash2 size={16} />
-                                    </button>
-                                </td>
-                            </tr>
-                        ))}
+                                        {result.ticker}
+                                        {result.exDividend && (
+                                            <span
+                                                className={`ex-dividend-badge${result.alert ? ' on-signal' : ''}`}
+                                                title={`${result.alert ? '배당락 전후의 시그널입니다. 하락은 배당 때문일 수 있습니다. ' : ''}배당락일 ${result.exDividend.date.slice(0, 10)} (주당 ${result.exDividend.amount})`}
+                                            >
+                                                배당락 {result.exDividend.date.slice(5, 10)}
+                                            </span>
+                                        )}
+                                    </td>
+                                    <td>
+                                        {price !== undefined
+                                            ? formatPrice(price, currency)
+                                            : '-'}
+                                        {changePercent != null && (
+                                            <span className={`price-change ${changePercent >= 0 ? 'up' : 'down'}`}>
+                                                {changePercent >= 0 ? '+' : ''}{changePercent.toFixed(2)}%
+                                            </span>
+                                        )}
+                                        {convertedPrice !== undefined && converted && (
+                                            <div className="price-converted">
+                                                ≈ {formatPrice(convertedPrice, converted.currency)}
+                                            </div>
+                                        )}
+                                    </td>
+                                    <td className={result.rsi !== undefined && result.rsi < settings.rsiTripleSignal ? 'alert-value' : ''}>
+                                        {result.rsi !== undefined ? result.rsi.toFixed(1) : '-'}
+                                    </td>
+                                    <td className={result.mfi !== undefined && result.mfi < settings.mfiTripleSignal ? 'alert-value' : ''}>
+                                        {result.mfi !== undefined ? result.mfi.toFixed(1) : '-'}
+                                    </td>
+                                    <td className="md:hidden">
+                                        <span className={result.bb_touch ? 'alert-value' : 'inactive-value'}>
+                                            {result.bb_touch ? '하단👇' : '―'}
+                                        </span>
+                                    </td>
+                                    <td className="desktop-only">
+                                        <span className={result.bb_touch ? 'alert-value' : 'inactive-value'}>
+                                            {result.bb_touch ? '하단 터치 👇' : '범위 내'}
+                                        </span>
+                                    </td>
+                                    <td style={{ textAlign: 'center' }}>
+                                        <button
+                                            onClick={() => onRemove(result.ticker)}
+                                            className="remove-btn"
+                                            title="티커 삭제"
+                                            disabled={isAnalyzing}
+                                        >
+                                            <Trash2 size={16} />
+                                        </button>
+                                    </td>
+                                </tr>
+                            );
+                        })}
                     </tbody>
                 </table>
             </div>
@@ -122,7 +154,7 @@ export function ResultTable({ results, activeTab, isAnalyzing, failedTickers, on
                         </div>
                         <div style={{ display: 'flex', flexDirection: 'column', gap: '0.5rem' }}>
                             {results.filter(r => r.error).map(r => {
-                                const isRateLimit = r.error?.includes('API_RATE_LIMIT');
+                                const isRateLimit = r.errorCode === 'API_RATE_LIMIT' || r.error?.includes('API_RATE_LIMIT');
                                 const isBlocked = r.error?.includes('API_BLOCKED');
                                 return (
                                     <div key={r.ticker} className={`error-item ${isRateLimit || isBlocked ? 'warning' : ''}`}>
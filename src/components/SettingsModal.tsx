@@ -4,6 +4,14 @@ import { Dialog, Portal, Slider, NumberInput } from '@ark-ui/react';
 import type { AnalysisSettings } from '@/types/settings';
 import '../styles/components/SettingsModal.css';
 
+/** Settings edited as free-form number inputs */
+type NumericSettingKey = {
+    [K in keyof AnalysisSettings]-?: AnalysisSettings[K] extends number ? K : never
+}[keyof AnalysisSettings];
+
+/** Base currency choices; '' keeps each ticker's own currency */
+const BASE_CURRENCIES = ['', 'KRW', 'USD', 'JPY', 'EUR'];
+
 interface SettingsModalProps {
     isOpen: boolean;
     onClose: () => void;
@@ -13,7 +21,8 @@ interface SettingsModalProps {
 }
 
 export function SettingsModal({ isOpen, onClose, settings, onSave, onReset }: SettingsModalProps) {
-    const [inputValues, setInputValues] = useState<Record<keyof AnalysisSettings, string>>({
+    const [baseCurrency, setBaseCurrency] = useState(settings.baseCurrency ?? '');
+    const [inputValues, setInputValues] = useState<Record<NumericSettingKey, string>>({
         rsiPeriod: settings.rsiPeriod.toString(),
         rsiOversold: settings.rsiOversold.toString(),
         rsiTripleSignal: settings.rsiTripleSignal.toString(),
@@ -29,6 +38,7 @@ export function SettingsModal({ isOpen, onClose, settings, onSave, onReset }: Se
         if (isOpen) {
             // Avoid synchronous setState in effect to prevent cascading renders
             requestAnimationFrame(() => {
+                setBaseCurrency(settings.baseCurrency ?? '');
                 setInputValues({
                     rsiPeriod: settings.rsiPeriod.toString(),
                     rsiOversold: settings.rsiOversold.toString(),
@@ -44,7 +54,7 @@ export function SettingsModal({ isOpen, onClose, settings, onSave, onReset }: Se
         }
     }, [isOpen, settings]);
 
-    const handleChange = (key: keyof AnalysisSettings, value: string) => {
+    const handleChange = (key: NumericSettingKey, value: string) => {
         setInputValues(prev => ({ ...prev, [key]: value }));
 
         // Live Preview for Opacity
@@ -62,8 +72,8 @@ export function SettingsModal({ isOpen, onClose, settings, onSave, onReset }: Se
     };
 
     const handleSave = () => {
-        const newSettings: AnalysisSettings = { ...settings };
-        (Object.keys(inputValues) as Array<keyof AnalysisSettings>).forEach(key => {
+        const newSettings: AnalysisSettings = { ...settings, baseCurrency: baseCurrency || undefined };
+        (Object.keys(inputValues) as Array<NumericSettingKey>).forEach(key => {
             const val = parseFloat(inputValues[key]);
             if (!isNaN(val)) newSettings[key] = val;
         });
@@ -200,6 +210,22 @@ export function SettingsModal({ isOpen, onClose, settings, onSave, onReset }: Se
                                     </div>
                                 </div>
                             </section>
+
+                            <section className="settings-section">
+                                <h3 className="settings-section-title">기준 통화</h3>
+                                <div>
+                                    <label className="settings-field-label">환산 통화 (다음 분석부터 적용)</label>
+                                    <select
+                                        className="settings-input"
+                                        value={baseCurrency}
+                                        onChange={e => setBaseCurrency(e.target.value)}
+                                    >
+                                        {BASE_CURRENCIES.map(code => (
+                                            <option key={code} value={code}>{code || '환산 안 함'}</option>
+                                        ))}
+                                    </select>
+                                </div>
+                            </section>
                         </div>
 
                         <footer className="settings-footer">
@@ -8,6 +8,10 @@ interface TickerInputProps {
     onAdd: () => void;
     onKeyDown: (e: React.KeyboardEvent) => void;
     isAnalyzing: boolean;
+    /** Shown under the field, e.g. when the ticker is not listed */
+    error?: string | null;
+    /** Ticker lookup in progress */
+    isValidating?: boolean;
 }
 
 export function TickerInput({
@@ -15,30 +19,35 @@ export function TickerInput({
     onInputChange,
     onAdd,
     onKeyDown,
-    isAnalyzing
+    isAnalyzing,
+    error,
+    isValidating = false
 }: TickerInputProps) {
     return (
-        <div className="ticker-input-root">
-            <input
-                type="text"
-                className="ticker-input-field"
-                value={inputValue}
-                onChange={(e) => onInputChange(e.target.value)}
-                onKeyPress={(e) => {
-                    if (e.key === 'Enter') {
-                        onKeyDown(e);
-                    }
-                }}
-                placeholder="티커 입력 (예: AAPL)"
-                disabled={isAnalyzing}
-            />
-            <button
-                className="ticker-add-button"
-                onClick={onAdd}
-                disabled={isAnalyzing}
-            >
-                <Plus size={18} /> 추가
-            </button>
+        <div className="ticker-input-wrapper">
+            <div className={`ticker-input-root${error ? ' has-error' : ''}`}>
+                <input
+                    type="text"
+                    className="ticker-input-field"
+                    value={inputValue}
+                    onChange={(e) => onInputChange(e.target.value)}
+                    onKeyPress={(e) => {
+                        if (e.key === 'Enter') {
+                            onKeyDown(e);
+                        }
+                    }}
+                    placeholder="티커 입력 (예: AAPL)"
+                    disabled={isAnalyzing}
+                />
+                <button
+                    className="ticker-add-button"
+                    onClick={onAdd}
+                    disabled={isAnalyzing || isValidating}
+                >
+                    <Plus size={18} /> {isValidating ? '확인 중' : '추가'}
+                </button>
+            </div>
+            {error && <p className="ticker-input-error">{error}</p>}
         </div>
     );
 }
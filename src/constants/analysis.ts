@@ -35,6 +35,7 @@ export const UI_CONFIG = {
 
     // Auto-refresh
     MARKET_INDICATORS_REFRESH_INTERVAL_MS: 5 * 60 * 1000, // 5 minutes
+    QUOTES_REFRESH_INTERVAL_MS: 10 * 1000, // 10 seconds
 
     // Progress display
     PROGRESS_MESSAGE_DISPLAY_MS: 3000,
@@ -3,3 +3,4 @@ export * from './useTickers';
 export * from './useAnalysis';
 export * from './useAppLifecycle';
 export * from './useSettings';
+export * from './useQuotes';
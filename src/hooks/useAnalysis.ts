@@ -1,5 +1,5 @@
 import { useState, useRef, useEffect } from 'react';
-import type { AnalysisResult, AnalysisModeType, TabType, TauriAnalysisResult } from '@/types';
+import type { AnalysisResult, AnalysisModeType, BatchEvent, TabType, TauriAnalysisResult } from '@/types';
 import type { AnalysisSettings } from '@/types/settings';
 import { UI_CONFIG } from '@/constants';
 import { delay } from '@/lib/utils/async';
@@ -20,6 +20,8 @@ export function useAnalysis(tickers: string[], settings: AnalysisSettings) {
     // const [shouldStop, setShouldStop] = useState(false); // Redundant with shouldStopRef
 
     const abortControllerRef = useRef<AbortController | null>(null);
+    // Id of the running Tauri batch job (for cancel_job)
+    const jobIdRef = useRef<string | null>(null);
     // Refs for immediate access in loops
     const isPausedRef = useRef(false);
     const shouldStopRef = useRef(false);
@@ -113,6 +115,12 @@ export function useAnalysis(tickers: string[], settings: AnalysisSettings) {
         if (abortControllerRef.current) {
             abortControllerRef.current.abort();
         }
+
+        // Abort the Tauri batch job; tickers already done keep their results
+        const jobId = jobIdRef.current;
+        if (jobId) {
+            import('@tauri-apps/api/core').then(({ invoke }) => invoke('cancel_job', { jobId }));
+        }
     };
 
     // Helper to check pause state and wait if needed
@@ -161,38 +169,29 @@ export function useAnalysis(tickers: string[], settings: AnalysisSettings) {
 
         if (isTauri) {
             try {
-                // Batch Analysis for Tauri
-                const { invoke } = await import('@tauri-apps/api/core');
+                // Batch Analysis for Tauri: results stream in as each ticker finishes
+                const { invoke, Channel } = await import('@tauri-apps/api/core');
 
-                // Show progress as "Processing..."
                 setProgress({ current: 0, total: targetTickers.length, currentTicker: 'Analyzing...' });
 
-                const chunkSize = 5;
-                const chunks = [];
-                for (let i = 0; i < targetTickers.length; i += chunkSize) {
-                    chunks.push(targetTickers.slice(i, i + chunkSize));
-                }
-
-                let processedCount = 0;
-
-                for (const chunk of chunks) {
-                    if (shouldStopRef.current) break; // Check stop before next chunk
-
-                    // Show progress updating for current chunk
-                    setProgress({
-                        current: processedCount,
-                        total: targetTickers.length,
-                        currentTicker: `Analyzing... (${processedCount}/${targetTickers.length})`
-                    });
-
-                    // Add small delay between chunks to let UI breathe
-                    if (processedCount > 0) await delay(200);
-
-                    const batchResults = await invoke<TauriAnalysisResult[]>('analyze_multiple_stocks', { tickers: chunk, settings });
-
-                    const processedResults = batchResults.map(r => {
-                        const analysisResult: AnalysisResult = {
+                await new Promise<void>((resolve, reject) => {
+                    const onEvent = new Channel<BatchEvent<TauriAnalysisResult>>();
+                    // A short job can finish before invoke resolves with its id
+                    let finished = false;
+                    onEvent.onmessage = (message) => {
+                        if (message.event === 'finished') {
+                            finished = true;
+                            jobIdRef.current = null;
+                            resolve();
+                            return;
+                        }
+                        if (message.event !== 'ticker' || message.status === 'cancelled') return;
+
+                        const r = message.result;
+                        const analysisResult: AnalysisResult = r ? {
                             ...r,
+                            error: r.error?.message,
+                            errorCode: r.error?.code,
                             alert: false,
                             rsi: r.rsi,
                             mfi: r.mfi,
@@ -201,34 +200,38 @@ export function useAnalysis(tickers: string[], settings: AnalysisSettings) {
                             bb_lower: r.bollingerLower,
                             bb_upper: r.bollingerUpper,
                             bb_middle: r.bollingerMiddle,
+                        } : {
+                            ticker: message.ticker,
+                            alert: false,
+                            error: message.error?.message ?? 'Analysis failed',
+                            errorCode: message.error?.code,
                         };
-                        return recalculateResult(analysisResult, settings);
-                    });
-
-                    setResults(prev => {
-                        // Remove old results for these tickers to avoid dups
-                        const chunkTickers = chunk;
-                        const filtered = prev.filter(r => !chunkTickers.includes(r.ticker));
-                        return [...filtered, ...processedResults];
-                    });
-
-                    // Update failed tickers based on batch results
-                    processedResults.forEach(r => {
-                        if (r.error) setFailedTickers(prev => [...prev, r.ticker]);
-                    });
-
-                    processedCount += chunk.length;
-
-                    // Update progress to show completion of this chunk
-                    setProgress({
-                        current: processedCount,
-                        total: targetTickers.length,
-                        currentTicker: `Analyzing... (${processedCount}/${targetTickers.length})`
-                    });
-                }
-
-
-
+                        const processed = recalculateResult(analysisResult, settings);
+
+                        // Keep rows in input order even though tickers finish out of order
+                        const position = (ticker: string) => targetTickers.indexOf(ticker);
+                        setResults(prev => [...prev.filter(p => p.ticker !== message.ticker), processed]
+                            .sort((a, b) => position(a.ticker) - position(b.ticker)));
+                        if (message.status === 'failed') setFailedTickers(prev => [...prev, message.ticker]);
+
+                        setProgress({
+                            current: message.completed,
+                            total: message.total,
+                            currentTicker: message.status === 'retrying'
+                                ? `${message.ticker} (retrying shortly)`
+                                : message.ticker
+                        });
+                    };
+
+                    invoke<string>('analyze_multiple_stocks', { tickers: targetTickers, settings, onEvent })
+                        .then(jobId => {
+                            if (finished) return;
+                            jobIdRef.current = jobId;
+                            // Stop was pressed before the job id arrived
+                            if (shouldStopRef.current) invoke('cancel_job', { jobId });
+                        })
+                        .catch(reject);
+                });
             } catch (error) {
                 console.error('Batch analysis failed:', error);
                 // Fallback to error state for all
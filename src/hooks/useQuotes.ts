@@ -0,0 +1,52 @@
+import { useState, useEffect } from 'react';
+import { isTauriEnvironment } from '@/lib/utils/platform';
+import type { QuoteResult, QuoteSnapshot } from '@/types';
+import { UI_CONFIG } from '@/constants';
+
+/**
+ * Live quotes for `tickers`, refreshed every few seconds (Tauri only).
+ * Much cheaper than re-running the analysis, which stays on its own schedule.
+ */
+export function useQuotes(tickers: string[]) {
+    const [quotes, setQuotes] = useState<Record<string, QuoteSnapshot>>({});
+    const tickerKey = tickers.join(',');
+
+    useEffect(() => {
+        if (!isTauriEnvironment() || !tickerKey) return;
+        const targetTickers = tickerKey.split(',');
+        let cancelled = false;
+        // A slow poll must not overlap the next tick
+        let inFlight = false;
+
+        const fetchQuotes = async () => {
+            // No point polling while the window is hidden
+            if (document.hidden || inFlight) return;
+            inFlight = true;
+            try {
+                const { invoke } = await import('@tauri-apps/api/core');
+                const results = await invoke<QuoteResult[]>('fetch_quotes', { tickers: targetTickers });
+                if (cancelled) return;
+                setQuotes(prev => {
+                    const next = { ...prev };
+                    for (const r of results) {
+                        if (r.quote) next[r.ticker] = r.quote;
+                    }
+                    return next;
+                });
+            } catch (err) {
+                console.error('Failed to fetch quotes:', err);
+            } finally {
+                inFlight = false;
+            }
+        };
+
+        fetchQuotes();
+        const intervalId = setInterval(fetchQuotes, UI_CONFIG.QUOTES_REFRESH_INTERVAL_MS);
+        return () => {
+            cancelled = true;
+            clearInterval(intervalId);
+        };
+    }, [tickerKey]);
+
+    return quotes;
+}
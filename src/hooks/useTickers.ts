@@ -2,10 +2,13 @@ import { useState, useEffect } from 'react';
 import { BaseDirectory, readTextFile, writeTextFile, exists } from '@tauri-apps/plugin-fs';
 import { isTauriEnvironment, isNativeEnvironment, isCapacitorEnvironment } from '@/lib/utils/platform';
 import { DEFAULT_PRESETS } from '@/constants/presets';
+import type { SymbolMatch, TauriError } from '@/types';
 
 export function useTickers() {
     const [tickers, setTickers] = useState<string[]>([]);
     const [inputValue, setInputValue] = useState('');
+    const [inputError, setInputError] = useState<string | null>(null);
+    const [isValidating, setIsValidating] = useState(false);
     const [showAllTickers, setShowAllTickers] = useState(false);
     const [loaded, setLoaded] = useState(false);
 
@@ -41,12 +44,39 @@ export function useTickers() {
         }
     }, [tickers, loaded]);
 
-    const addTicker = () => {
+    const updateInputValue = (value: string) => {
+        setInputValue(value);
+        setInputError(null);
+    };
+
+    const addTicker = async () => {
         const ticker = inputValue.trim().toUpperCase();
-        if (ticker && !tickers.includes(ticker)) {
-            setTickers(prev => [...prev, ticker]);
-            setInputValue('');
+        if (!ticker || tickers.includes(ticker) || isValidating) return;
+
+        // Tauri: make sure the ticker is actually listed before saving it.
+        // Only a definite "no such symbol" blocks it; if the lookup itself
+        // fails (offline, throttled) the ticker is added unchecked.
+        if (isTauriEnvironment()) {
+            setIsValidating(true);
+            try {
+                const { invoke } = await import('@tauri-apps/api/core');
+                const match = await invoke<SymbolMatch>('validate_symbol', { ticker });
+                console.log(`✅ ${match.symbol}: ${match.name ?? ''} (${match.exchange ?? match.source})`);
+            } catch (e) {
+                const error = e as Partial<TauriError>;
+                if (error?.code === 'NOT_FOUND' || error?.code === 'INVALID_SYMBOL') {
+                    setInputError(`'${ticker}' 종목을 찾을 수 없습니다.`);
+                    return;
+                }
+                console.warn(`Could not validate ${ticker}, adding anyway:`, e);
+            } finally {
+                setIsValidating(false);
+            }
         }
+
+        setTickers(prev => (prev.includes(ticker) ? prev : [...prev, ticker]));
+        setInputValue('');
+        setInputError(null);
     };
 
     const removeTicker = async (ticker: string, alsoRemoveFromPreset: boolean = false) => {
@@ -186,7 +216,9 @@ export function useTickers() {
         tickers,
         setTickers,
         inputValue,
-        setInputValue,
+        setInputValue: updateInputValue,
+        inputError,
+        isValidating,
         showAllTickers,
         setShowAllTickers,
         addTicker,
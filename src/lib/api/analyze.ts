@@ -9,6 +9,8 @@ interface StockData {
     highs: number[];
     lows: number[];
     volumes: number[];
+    /** Quote currency from the chart meta (e.g. 'USD', 'KRW') */
+    currency?: string;
 }
 
 // Simple in-memory cache
@@ -111,7 +113,8 @@ async function getStockData(ticker: string): Promise<{ data: StockData; cached:
         adjCloses: validIndices.map(i => adjCloseData[i] || quotes.close[i]),
         highs: validIndices.map(i => quotes.high[i]),
         lows: validIndices.map(i => quotes.low[i]),
-        volumes: validIndices.map(i => quotes.volume[i])
+        volumes: validIndices.map(i => quotes.volume[i]),
+        currency: result.meta?.currency ?? undefined
     };
 
     // Update Cache
@@ -150,6 +153,7 @@ export async function analyzeStockClient(ticker: string): Promise<AnalysisResult
             bb_upper: bb.upper,
             bb_middle: bb.middle,
             price: latestPrice,
+            currency: stockData.currency,
             cached
         };
 
@@ -178,7 +178,7 @@ export async function analyzeTicker(ticker: string): Promise<TauriAnalysisResult
                 bollingerUpper: 0,
                 bollingerMiddle: 0,
                 tripleSignal: false,
-                error: 'Unable to fetch data',
+                error: { code: 'NOT_FOUND', message: 'Unable to fetch data' },
             };
         }
 
@@ -215,7 +215,10 @@ export async function analyzeTicker(ticker: string): Promise<TauriAnalysisResult
             bollingerUpper: 0,
             bollingerMiddle: 0,
             tripleSignal: false,
-            error: error instanceof Error ? error.message : 'Unknown error',
+            error: {
+                code: 'NETWORK_ERROR',
+                message: error instanceof Error ? error.message : 'Unknown error',
+            },
         };
     }
 }
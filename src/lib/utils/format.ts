@@ -0,0 +1,31 @@
+/**
+ * Formatting Utilities
+ * Display helpers for prices in their own currency
+ */
+
+/** Currencies Yahoo quotes in minor units: code -> [major code, major per minor] */
+const MINOR_UNITS: Record<string, [string, number]> = {
+    GBp: ['GBP', 0.01],
+    GBX: ['GBP', 0.01],
+    ZAc: ['ZAR', 0.01],
+    ILA: ['ILS', 0.01],
+};
+
+/**
+ * Format a price with its currency symbol (`$123.45`, `₩71,500`, `123.4p`).
+ * Without a known currency the plain number is shown.
+ */
+export function formatPrice(value: number, currency?: string | null): string {
+    if (!currency) return value.toFixed(2);
+
+    // Pence and other minor units have no Intl currency code
+    if (MINOR_UNITS[currency]) {
+        return `${value.toFixed(2)}${currency === 'GBp' || currency === 'GBX' ? 'p' : ` ${currency}`}`;
+    }
+
+    try {
+        return new Intl.NumberFormat('ko-KR', { style: 'currency', currency }).format(value);
+    } catch {
+        return `${value.toFixed(2)} ${currency}`;
+    }
+}
@@ -5,3 +5,4 @@
 
 export * from './platform';
 export * from './async';
+export * from './format';
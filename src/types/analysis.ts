@@ -25,8 +25,16 @@ export interface AnalysisResult {
   bb_middle?: number;
   /** Current price (real-time or delayed) */
   price?: number;
+  /** Currency of `price` and the bands (e.g. 'USD', 'KRW', 'GBp') */
+  currency?: string | null;
+  /** Prices in the base currency from settings (Tauri backend only) */
+  converted?: ConvertedPrices | null;
+  /** Ex-dividend date within a few days of the last bar (Tauri backend only) */
+  exDividend?: Dividend | null;
   /** Error message if analysis fails */
   error?: string;
+  /** Stable error code when the error came from the Tauri backend */
+  errorCode?: TauriErrorCode;
   /** Whether data is from memory/local cache */
   cached?: boolean;
 }
@@ -35,7 +43,7 @@ export interface AnalysisResult {
  * Historical price data structure for chart analysis
  */
 export interface HistoricalData {
-  /** Array of dates (ISO String: YYYY-MM-DD) */
+  /** Array of dates (ISO String: YYYY-MM-DD; Tauri returns RFC 3339 bar times with exchange offset) */
   dates: string[];
   /** Open prices */
   opens: number[];
@@ -49,6 +57,113 @@ export interface HistoricalData {
   adj_closes: number[];
   /** Volume data */
   volumes: number[];
+  /** Cash dividends by ex-date (Tauri backend only) */
+  dividends?: Dividend[];
+  /** Stock splits by effective date (Tauri backend only) */
+  splits?: Split[];
+  /** Currency of the prices, when the provider reports it (Tauri backend only) */
+  currency?: string | null;
+}
+
+/** Payload of the Tauri `history-updated` event (background refresh after a stale read) */
+export interface HistoryUpdatedEvent {
+  ticker: string;
+  /** The history request the refreshed series answers (range, interval, ...) */
+  request: Record<string, unknown>;
+  data: HistoricalData;
+}
+
+/** `retrying`: failed transiently, another attempt follows after a cool-down */
+export type TickerStatus = 'done' | 'failed' | 'retrying' | 'cancelled';
+
+/** Where one attempt at a ticker spent its time; unreached stages are absent */
+export interface TickerTiming {
+  queueMs?: number | null;
+  fetchMs?: number | null;
+  analysisMs?: number | null;
+  totalMs: number;
+}
+
+/** Final outcome of one batch ticker (without the result payload) */
+export interface TickerOutcome {
+  ticker: string;
+  /** Position in the request */
+  index: number;
+  status: TickerStatus;
+  attempts: number;
+  error: TauriError | null;
+  timing: TickerTiming;
+}
+
+/** How many tickers a batch ran at once as its adaptive limit moved */
+export interface ConcurrencySummary {
+  initial: number;
+  min: number;
+  max: number;
+  /** Limit when the batch ended */
+  last: number;
+  /** Attempts that hit a rate limit or an open circuit */
+  throttled: number;
+}
+
+/**
+ * Streamed over the `onEvent` channel of `analyze_multiple_stocks` /
+ * `fetch_multiple_stocks`; `ticker` events arrive in completion order, and a
+ * ticker that reported `retrying` sends another event from the retry pass
+ */
+export type BatchEvent<T> =
+  | { event: 'started'; jobId: string; total: number }
+  | {
+      event: 'ticker';
+      jobId: string;
+      ticker: string;
+      /** Position in the request */
+      index: number;
+      status: TickerStatus;
+      /** 1 for the first pass, 2 for the retry pass */
+      attempt: number;
+      result: T | null;
+      error: TauriError | null;
+      timing: TickerTiming;
+      completed: number;
+      total: number;
+    }
+  | {
+      event: 'finished';
+      jobId: string;
+      total: number;
+      succeeded: number;
+      failed: number;
+      cancelled: number;
+      retried: number;
+      elapsedMs: number;
+      concurrency: ConcurrencySummary;
+      /** Every requested ticker, in request order */
+      outcomes: TickerOutcome[];
+    };
+
+/** Result prices converted to the base currency */
+export interface ConvertedPrices {
+  currency: string;
+  /** Units of `currency` per unit of the result's currency */
+  rate: number;
+  currentPrice: number;
+  bollingerLower: number;
+  bollingerUpper: number;
+  bollingerMiddle: number;
+}
+
+/** Cash dividend; `date` is the ex-dividend date (RFC 3339) */
+export interface Dividend {
+  date: string;
+  amount: number;
+}
+
+/** Stock split, e.g. 4-for-1 is numerator 4, denominator 1 */
+export interface Split {
+  date: string;
+  numerator: number;
+  denominator: number;
 }
 
 /**
@@ -65,7 +180,164 @@ export interface TauriAnalysisResult {
   bollingerUpper: number;
   bollingerMiddle: number;
   tripleSignal: boolean;
-  error?: string;
+  /** Provider that served the price history (e.g. "yahoo", "stooq") */
+  dataSource?: string;
+  /** Completeness of the fetched history (null bars, gaps, zero-volume days) */
+  dataQuality?: DataQualityReport;
+  /** Ex-dividend date within a few days of the last bar, if any */
+  exDividend?: Dividend | null;
+  /** Currency of `currentPrice` and the bands */
+  currency?: string | null;
+  /** Same prices in `AnalysisSettings.baseCurrency`, when requested */
+  converted?: ConvertedPrices | null;
+  error?: TauriError | null;
+}
+
+/** Stable error codes from the Rust backend (switch on these, not on `message`) */
+export type TauriErrorCode =
+  | 'API_RATE_LIMIT'
+  | 'CIRCUIT_OPEN'
+  | 'HTTP_ERROR'
+  | 'NETWORK_ERROR'
+  | 'PARSE_ERROR'
+  | 'NOT_FOUND'
+  | 'INVALID_SYMBOL'
+  | 'DELISTED_SYMBOL'
+  | 'INSUFFICIENT_DATA'
+  | 'INVALID_SETTINGS'
+  | 'INVALID_DATA'
+  | 'UNSUPPORTED'
+  | 'STORAGE_ERROR'
+  | 'TASK_PANIC';
+
+/** Structured error returned by Tauri commands and inside analysis results */
+export interface TauriError {
+  code: TauriErrorCode;
+  /** English description (fallback when a code has no localized text) */
+  message: string;
+  /** API_RATE_LIMIT / CIRCUIT_OPEN: seconds to wait, when known */
+  retryAfter?: number;
+  /** HTTP_ERROR: response status */
+  status?: number;
+  /** INSUFFICIENT_DATA: bars required / available */
+  needed?: number;
+  have?: number;
+  detail?: string;
+}
+
+/** Circuit breaker state of an upstream host (`get_upstream_status`) */
+export interface UpstreamHostStatus {
+  host: string;
+  state: 'closed' | 'open' | 'halfOpen';
+  consecutiveFailures: number;
+  /** Seconds until the next probe request (open circuits only) */
+  retryInSecs?: number | null;
+}
+
+/** One cached series, from `get_cache_stats` */
+export interface CacheItemStats {
+  key: string;
+  ticker: string;
+  source: string;
+  bars: number;
+  ageSecs: number;
+  /** Negative once expired */
+  expiresInSecs: number;
+  approxBytes: number;
+}
+
+/** History cache counters and contents, from `get_cache_stats` */
+export interface CacheStats {
+  hits: number;
+  staleHits: number;
+  misses: number;
+  evictions: number;
+  entries: number;
+  approxBytes: number;
+  maxEntries: number;
+  maxBytes: number;
+  /** Most recently used first */
+  items: CacheItemStats[];
+}
+
+export type AssetType =
+  | 'equity'
+  | 'etf'
+  | 'mutualFund'
+  | 'index'
+  | 'crypto'
+  | 'currency'
+  | 'future'
+  | 'other';
+
+/** Trading session at the time of a quote */
+export type MarketState = 'pre' | 'regular' | 'post' | 'closed';
+
+/** Last price of one ticker, from `fetch_quotes` */
+export interface QuoteSnapshot {
+  ticker: string;
+  price: number;
+  previousClose?: number | null;
+  /** `price - previousClose` */
+  change?: number | null;
+  changePercent?: number | null;
+  dayHigh?: number | null;
+  dayLow?: number | null;
+  volume?: number | null;
+  marketState: MarketState;
+  currency?: string | null;
+  /** When `price` was last traded (RFC 3339, exchange time) */
+  timestamp?: string | null;
+  source: string;
+}
+
+/** One `fetch_quotes` entry; exactly one of `quote` / `error` is set */
+export interface QuoteResult {
+  ticker: string;
+  quote?: QuoteSnapshot | null;
+  error?: TauriError | null;
+}
+
+/** One `search_symbols` / `validate_symbol` hit */
+export interface SymbolMatch {
+  /** Canonical ticker (`BRK.B`, `005930.KS`) */
+  symbol: string;
+  name?: string | null;
+  /** Exchange as the source names it (e.g. "NASDAQ", "KSE") */
+  exchange?: string | null;
+  assetType: AssetType;
+  currency?: string | null;
+  /** Provider that answered ("yahoo", "local", ...) */
+  source: string;
+}
+
+/** Per-ticker data completeness report from the Rust backend */
+export interface DataQualityReport {
+  gapPolicy: 'drop' | 'forwardFill' | 'markMissing';
+  bars: number;
+  nullBars: number;
+  droppedBars: number;
+  filledBars: number;
+  /** Weekdays without a bar (exchange holidays included) */
+  calendarGaps: number;
+  zeroVolumeBars: number;
+  /** Sanity-check findings; an `error` issue blocks analysis */
+  issues?: ValidationIssue[];
+}
+
+/** One class of malformed/implausible bars found in a series */
+export interface ValidationIssue {
+  kind:
+    | 'lengthMismatch'
+    | 'highBelowLow'
+    | 'closeOutsideRange'
+    | 'nonPositivePrice'
+    | 'suspiciousJump'
+    | 'staleData';
+  severity: 'warning' | 'error';
+  count: number;
+  firstDate?: string | null;
+  message: string;
 }
 
 /** Tab categories for analysis results */
@@ -24,6 +24,14 @@ export interface AnalysisSettings {
     /** BB standard deviation multiplier. 1.0 recommended for Triple Signal. */
     bbStdDev: number;
 
+    // --- Intraday Settings ---
+    /** Compute indicators on regular-session bars only (ignore pre/post-market) */
+    excludeExtendedHours?: boolean;
+
+    // --- Currency Settings ---
+    /** Also show prices converted to this currency (e.g. 'KRW'); unset shows native prices only */
+    baseCurrency?: string;
+
     // --- UI Settings ---
     /** Overlay mode opacity (0.1 ~ 1.0) */
     opacity: number;